@@ -0,0 +1,82 @@
+/// Times headless frames with over 10k boops orbiting, run with `cargo bench`. Rendering isn't
+/// included, boop sprites share one image so they're drawn as a single instanced batch anyway.
+/// Boop entities are timed on their own, then swarms, where past MAX_BOOP_SPRITES a loop only
+/// draws its swarm. Fails if either takes longer than FRAME_BUDGET a frame
+use bevy::prelude::*;
+use hoops_boops_and_loops::headless::*;
+use hoops_boops_and_loops::hoops_boops_loops::*;
+use std::time::{Duration, Instant};
+
+/// Every loop gets MAX_BOOP_SPRITES, so this many loops is 10240 boop entities
+const LOOPS: usize = 640;
+/// Every planet gets this many boops, all but MAX_BOOP_SPRITES of them in its swarm
+const BOOPS_PER_SWARM_LOOP: usize = 2000;
+const PLANETS: [Planet; 6] = [
+    Planet::One,
+    Planet::Two,
+    Planet::Three,
+    Planet::Four,
+    Planet::Five,
+    Planet::Six,
+];
+const WARMUP_FRAMES: u32 = 60;
+const FRAMES: u32 = 600;
+/// 60 fps
+const FRAME_BUDGET: Duration = Duration::from_micros(16_667);
+
+fn main() {
+    let mut app = headless_app();
+    for _ in 0..LOOPS {
+        spawn_full_loop(&mut app, Planet::One, MAX_BOOP_SPRITES);
+    }
+    let entities_in_budget = time_frames("boop entities", &mut app);
+
+    let mut app = headless_app();
+    for planet in PLANETS {
+        spawn_full_loop(&mut app, planet, BOOPS_PER_SWARM_LOOP);
+    }
+    let swarms_in_budget = time_frames("swarms", &mut app);
+
+    if !(entities_in_budget && swarms_in_budget) {
+        eprintln!("Over the {FRAME_BUDGET:?} frame budget");
+        std::process::exit(1);
+    }
+}
+
+fn spawn_full_loop(app: &mut App, planet: Planet, boops: usize) {
+    let (r#loop, _, _) = spawn_test_loop(app, planet);
+    for _ in 1..boops {
+        apply(app, AddBoop(r#loop)).unwrap();
+    }
+    for _ in 1..MAX_HOOPS {
+        apply(app, AddHoop(r#loop)).unwrap();
+    }
+}
+
+/// Prints the average frame time, returning whether it's within FRAME_BUDGET
+fn time_frames(name: &str, app: &mut App) -> bool {
+    for _ in 0..WARMUP_FRAMES {
+        app.update();
+    }
+
+    let start = Instant::now();
+    for _ in 0..FRAMES {
+        app.update();
+    }
+    let per_frame = start.elapsed() / FRAMES;
+
+    let boops: u64 = app
+        .world_mut()
+        .query::<&Loop>()
+        .iter(app.world())
+        .map(|r#loop| r#loop.boop_count())
+        .sum();
+    println!(
+        "{name}, {boops} boops ({} with sprites): {per_frame:?} per frame, {:.0} fps, {:.0}% of \
+         the {FRAME_BUDGET:?} budget",
+        count::<Boop>(app),
+        1. / per_frame.as_secs_f32(),
+        100. * per_frame.as_secs_f32() / FRAME_BUDGET.as_secs_f32()
+    );
+    per_frame <= FRAME_BUDGET
+}
@@ -0,0 +1,167 @@
+/// Shows at a glance whether things with a PriceTag can be bought. Unaffordable ones are dimmed,
+/// and affordable ones glow with a slow pulse. Tags with a ring also show how close the player is
+/// to affording them with dots filling up around them. Only rechecked when Loot or the Wallet
+/// changes, which happens on every hoop pass and purchase
+use crate::loot::Loot;
+use crate::wallet::{Price, Wallet};
+use bevy::prelude::*;
+use bevy_tweening::lens::SpriteColorLens;
+use bevy_tweening::{Animator, RepeatCount, RepeatStrategy, Tween};
+use std::f32::consts::PI;
+use std::time::Duration;
+
+const DIM_COLOR: Color = Color::srgba(0., 0., 0., 0.5);
+const GLOW_COLOR: Color = Color::srgb(0.333, 0.808, 0.929);
+const GLOW_SCALE: f32 = 1.12;
+const GLOW_PULSE: Duration = Duration::from_millis(900);
+const RING_DOTS: usize = 24;
+const RING_DOT_SIZE: f32 = 6.;
+
+/// Put on something that can be bought to show whether it's affordable
+#[derive(Component)]
+pub struct PriceTag {
+    /// None once there's nothing left to buy, which dims it for good
+    pub price: Option<Price>,
+    /// The image the dim and glow take the shape of, usually the entity's own
+    pub shape: Handle<Image>,
+    /// The radius of the progress ring, None for no ring
+    pub ring_radius: Option<f32>,
+}
+
+/// The children spawned for a PriceTag
+#[derive(Component)]
+struct AffordabilityParts {
+    dim: Entity,
+    glow: Entity,
+    ring: Vec<Entity>,
+}
+
+pub fn plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        update_affordability.run_if(
+            resource_changed::<Loot>
+                .or(resource_changed::<Wallet>)
+                .or(any_match_filter::<Added<AffordabilityParts>>),
+        ),
+    )
+    .add_observer(spawn_affordability_parts);
+}
+
+/// The dim goes over every other child, the glow behind the entity
+fn spawn_affordability_parts(
+    t: Trigger<OnAdd, PriceTag>,
+    tag_q: Query<&PriceTag>,
+    mut commands: Commands,
+) {
+    let Ok(tag) = tag_q.get(t.target()) else {
+        return;
+    };
+
+    let dim = commands
+        .spawn((
+            Sprite {
+                image: tag.shape.clone(),
+                color: DIM_COLOR,
+                ..default()
+            },
+            Transform::from_xyz(0., 0., 2.),
+            Visibility::Hidden,
+            Pickable::IGNORE,
+        ))
+        .id();
+
+    let pulse = Tween::new(
+        EaseFunction::SineInOut,
+        GLOW_PULSE,
+        SpriteColorLens {
+            start: GLOW_COLOR.with_alpha(0.1),
+            end: GLOW_COLOR.with_alpha(0.6),
+        },
+    )
+    .with_repeat_count(RepeatCount::Infinite)
+    .with_repeat_strategy(RepeatStrategy::MirroredRepeat);
+    let glow = commands
+        .spawn((
+            Sprite {
+                image: tag.shape.clone(),
+                color: GLOW_COLOR.with_alpha(0.),
+                ..default()
+            },
+            Transform {
+                translation: Vec3::new(0., 0., -0.5),
+                scale: Vec3::splat(GLOW_SCALE),
+                ..default()
+            },
+            Animator::new(pulse),
+            Visibility::Hidden,
+            Pickable::IGNORE,
+        ))
+        .id();
+
+    // Clockwise from the top, like a clock hand
+    let ring: Vec<Entity> = tag
+        .ring_radius
+        .map(|radius| {
+            (0..RING_DOTS)
+                .map(|i| {
+                    let angle = PI / 2. - 2. * PI * i as f32 / RING_DOTS as f32;
+                    commands
+                        .spawn((
+                            Sprite::from_color(GLOW_COLOR, Vec2::splat(RING_DOT_SIZE)),
+                            Transform::from_translation(
+                                (Vec2::from_angle(angle) * radius).extend(1.5),
+                            ),
+                            Visibility::Hidden,
+                            Pickable::IGNORE,
+                        ))
+                        .id()
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    commands
+        .entity(t.target())
+        .add_children(&[dim, glow])
+        .add_children(&ring)
+        .insert(AffordabilityParts { dim, glow, ring });
+}
+
+fn update_affordability(
+    loot: Res<Loot>,
+    wallet: Res<Wallet>,
+    tag_q: Query<(&PriceTag, &AffordabilityParts)>,
+    mut visibility_q: Query<&mut Visibility>,
+) {
+    for (tag, parts) in tag_q {
+        let progress = tag
+            .price
+            .map(|price| price.progress(**loot, &wallet))
+            .unwrap_or(0.);
+        let affordable = tag.price.is_some() && progress >= 1.;
+
+        let mut show = |entity: Entity, shown: bool| {
+            if let Ok(mut visibility) = visibility_q.get_mut(entity) {
+                visibility.set_if_neq(if shown {
+                    Visibility::Inherited
+                } else {
+                    Visibility::Hidden
+                });
+            }
+        };
+
+        show(parts.dim, !affordable);
+        show(parts.glow, affordable);
+
+        // Nothing to fill up toward once it's affordable or there's nothing left
+        let filled = if affordable || tag.price.is_none() {
+            0
+        } else {
+            (progress * parts.ring.len() as f32).floor() as usize
+        };
+        for (i, dot) in parts.ring.iter().enumerate() {
+            show(*dot, i < filled);
+        }
+    }
+}
@@ -0,0 +1,25 @@
+use bevy::audio::Volume;
+use bevy::prelude::*;
+use bevy_tweening::*;
+
+/// Lens used for fading music in and out
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct AudioSinkVolumeLens {
+    /// The start and end as linear volume, 0 is silent and 1 is full volume
+    pub start: f32,
+    pub end: f32,
+}
+
+impl Lens<AudioSink> for AudioSinkVolumeLens {
+    fn lerp(&mut self, target: &mut dyn Targetable<AudioSink>, ratio: f32) {
+        let value = self.start + (self.end - self.start) * ratio;
+        target.target_mut().set_volume(Volume::Linear(value));
+    }
+}
+
+pub fn audio_sink_volume_lens_plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        component_animator_system::<AudioSink>.in_set(AnimationSystem::AnimationUpdate),
+    );
+}
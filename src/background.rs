@@ -1,30 +1,261 @@
+/// The backdrop behind everything: the light trail art and a few layers of stars. Far layers move
+/// less than near ones as the planets zoom in and out, and the stars slowly drift and twinkle. That
+/// goes by Time<Real>, so fast forwarding the game doesn't speed up the sky
+use crate::screen_size::SCREEN_SIZE;
+use crate::titlescreen::PLAY_BTN_LOCATION;
+use crate::transition_to_first_planet::{FIRST_PLANET_INITIAL_SCALE, FirstPlanet};
 use bevy::color::palettes::basic::BLACK;
 use bevy::prelude::*;
+use rand::Rng;
+use std::f32::consts::PI;
+
+/// The light trail art is the farthest layer. It's screen sized, and the world only ever zooms in
+/// from the titlescreen, so this has to stay small to keep its edges offscreen
+const LIGHT_TRAIL_PARALLAX: f32 = 0.05;
+
+/// Each layer's stars are spread over, and wrap around, a tile this many screens wide and high so
+/// zooming out a bit doesn't show where they end
+const TILE_SCREENS: f32 = 2.;
+
+struct StarLayer {
+    /// How much the layer moves with the world, 1 moves like the planets, 0 is stuck to the screen
+    parallax: f32,
+    /// In pixels per second
+    drift: Vec2,
+    size: f32,
+    brightness: f32,
+    /// Share of StarfieldSettings::density this layer gets
+    share: f32,
+}
+
+/// Farthest first
+const LAYERS: [StarLayer; 3] = [
+    StarLayer {
+        parallax: 0.1,
+        drift: Vec2::new(-2., 0.5),
+        size: 1.,
+        brightness: 0.4,
+        share: 0.6,
+    },
+    StarLayer {
+        parallax: 0.3,
+        drift: Vec2::new(-4., 1.),
+        size: 1.5,
+        brightness: 0.6,
+        share: 0.3,
+    },
+    StarLayer {
+        parallax: 0.6,
+        drift: Vec2::new(-8., 2.),
+        size: 2.5,
+        brightness: 0.9,
+        share: 0.1,
+    },
+];
+
+/// In radians per second
+const MIN_TWINKLE_SPEED: f32 = 0.5;
+const MAX_TWINKLE_SPEED: f32 = 3.;
+/// How much of a star's brightness twinkling takes away at its dimmest
+const TWINKLE_DEPTH: f32 = 0.6;
+
+#[derive(Resource)]
+pub struct StarfieldSettings {
+    /// Stars per screen, across every layer. Changing it respawns the stars
+    pub density: f32,
+}
+
+impl Default for StarfieldSettings {
+    fn default() -> Self {
+        StarfieldSettings { density: 250. }
+    }
+}
+
+/// How much a layer of the background moves with the world, see StarLayer::parallax
+#[derive(Component)]
+struct Parallax(f32);
+
+/// Parent of one of LAYERS' stars, index into LAYERS
+#[derive(Component)]
+struct StarLayerIndex(usize);
+
+#[derive(Component)]
+struct Star {
+    /// Where it started in its layer, before drifting and wrapping
+    home: Vec2,
+    twinkle_speed: f32,
+    twinkle_phase: f32,
+}
 
 pub fn background_plugin(app: &mut App) {
-    app.add_systems(Startup, (spawn_star_bg, set_background_color));
-}
-
-fn spawn_star_bg(mut commands: Commands, asset_server: ResMut<AssetServer>) {
-    commands.spawn((
-        Sprite::from_image(asset_server.load("light-trail-bg-1.png")),
-        Transform {
-            translation: Vec3::new(0., 0., -99.),
-            ..default()
-        },
-    ));
-
-    commands.spawn((
-        Sprite {
-            image: (asset_server.load("light-trail-bg-2.png")),
-            color: Color::WHITE.with_alpha(0.1),
-            ..default()
-        },
-        Transform {
-            translation: Vec3::new(0., 0., -99.),
-            ..default()
-        },
-    ));
+    app.init_resource::<StarfieldSettings>()
+        .add_systems(
+            Startup,
+            (
+                spawn_light_trail_bg,
+                spawn_star_layers,
+                set_background_color,
+            ),
+        )
+        .add_systems(
+            Update,
+            (
+                spawn_stars.run_if(resource_changed::<StarfieldSettings>),
+                follow_world_zoom,
+                drift_stars,
+                twinkle_stars,
+            )
+                .chain(),
+        );
+}
+
+fn spawn_light_trail_bg(mut commands: Commands, asset_server: ResMut<AssetServer>) {
+    commands
+        .spawn((
+            Parallax(LIGHT_TRAIL_PARALLAX),
+            Transform::from_xyz(0., 0., -99.),
+            Visibility::default(),
+        ))
+        .with_children(|parent| {
+            parent.spawn(Sprite::from_image(
+                asset_server.load("light-trail-bg-1.png"),
+            ));
+
+            parent.spawn(Sprite {
+                image: (asset_server.load("light-trail-bg-2.png")),
+                color: Color::WHITE.with_alpha(0.1),
+                ..default()
+            });
+        });
+}
+
+/// In front of the light trails, farthest layer first
+fn spawn_star_layers(mut commands: Commands) {
+    for (i, layer) in LAYERS.iter().enumerate() {
+        commands.spawn((
+            StarLayerIndex(i),
+            Parallax(layer.parallax),
+            Transform::from_xyz(0., 0., -98. + i as f32 * 0.1),
+            Visibility::default(),
+        ));
+    }
+}
+
+/// Replaces every layer's stars with StarfieldSettings::density worth of new ones. The stars are
+/// just decoration, so they come from rand::rng() and not GameRng
+fn spawn_stars(
+    settings: Res<StarfieldSettings>,
+    layer_q: Query<(Entity, &StarLayerIndex)>,
+    mut commands: Commands,
+) {
+    let mut rng = rand::rng();
+    let tile = SCREEN_SIZE * TILE_SCREENS;
+    let tile_screens = TILE_SCREENS * TILE_SCREENS;
+
+    for (layer_entity, layer_index) in layer_q {
+        let layer = &LAYERS[layer_index.0];
+        let count = (settings.density.max(0.) * layer.share * tile_screens).round() as usize;
+
+        commands
+            .entity(layer_entity)
+            .despawn_related::<Children>()
+            .with_children(|parent| {
+                for _ in 0..count {
+                    let home = Vec2::new(
+                        rng.random_range(-tile.x / 2.0..tile.x / 2.),
+                        rng.random_range(-tile.y / 2.0..tile.y / 2.),
+                    );
+                    parent.spawn((
+                        Star {
+                            home,
+                            twinkle_speed: rng.random_range(MIN_TWINKLE_SPEED..MAX_TWINKLE_SPEED),
+                            twinkle_phase: rng.random_range(0.0..2. * PI),
+                        },
+                        Sprite::from_color(
+                            Color::WHITE.with_alpha(layer.brightness),
+                            Vec2::splat(layer.size),
+                        ),
+                        Transform::from_translation(home.extend(0.)),
+                        Pickable::IGNORE,
+                    ));
+                }
+            });
+    }
+}
+
+/// The camera never moves, the transitions zoom and move the planets instead, so the first planet
+/// stands in for the camera. The zoom is how much it has grown since it was spawned on the
+/// titlescreen, and the pan is where the world would have to be looked at from to put it where it
+/// is. Nothing has zoomed before it exists
+fn world_zoom_and_pan(first_planet: Option<&Transform>) -> (f32, Vec2) {
+    let Some(planet) = first_planet else {
+        return (1., Vec2::ZERO);
+    };
+
+    let zoom = planet.scale.x / FIRST_PLANET_INITIAL_SCALE;
+    let pan = PLAY_BTN_LOCATION - planet.translation.truncate() / zoom;
+    (zoom, pan)
+}
+
+/// Layers zoom and pan with the planets by less than the planets do, the farther the less. A layer
+/// with no parallax doesn't move at all, so it looks stuck to the screen
+fn follow_world_zoom(
+    first_planet_q: Query<&Transform, (With<FirstPlanet>, Without<Parallax>)>,
+    layer_q: Query<(&Parallax, &mut Transform)>,
+) {
+    let (zoom, pan) = world_zoom_and_pan(first_planet_q.single().ok());
+    if zoom <= 0. {
+        return;
+    }
+
+    for (parallax, mut transform) in layer_q {
+        let parallax = parallax.0;
+        let scale = zoom.powf(parallax);
+        let translation = -pan * parallax * scale;
+        transform.translation.x = translation.x;
+        transform.translation.y = translation.y;
+        transform.scale = Vec3::splat(scale);
+    }
+}
+
+/// Moves stars by their layer's drift, wrapping them around the tile centered on the screen
+fn drift_stars(
+    layer_q: Query<(&StarLayerIndex, &Transform), Without<Star>>,
+    star_q: Query<(&Star, &ChildOf, &mut Transform)>,
+    time: Res<Time<Real>>,
+) {
+    let tile = SCREEN_SIZE * TILE_SCREENS;
+
+    for (star, child_of, mut transform) in star_q {
+        let Ok((layer_index, layer_transform)) = layer_q.get(child_of.parent()) else {
+            continue;
+        };
+
+        // Where the center of the screen is in the layer's own space. The camera never moves, so
+        // that's the world's origin
+        let center = -layer_transform.translation.truncate() / layer_transform.scale.truncate();
+        let drifted = star.home + LAYERS[layer_index.0].drift * time.elapsed_secs();
+        let wrapped = (drifted - center + tile / 2.).rem_euclid(tile) - tile / 2. + center;
+
+        transform.translation.x = wrapped.x;
+        transform.translation.y = wrapped.y;
+    }
+}
+
+fn twinkle_stars(
+    layer_q: Query<&StarLayerIndex>,
+    star_q: Query<(&Star, &ChildOf, &mut Sprite)>,
+    time: Res<Time<Real>>,
+) {
+    for (star, child_of, mut sprite) in star_q {
+        let Ok(layer_index) = layer_q.get(child_of.parent()) else {
+            continue;
+        };
+
+        let wave = (time.elapsed_secs() * star.twinkle_speed + star.twinkle_phase).sin();
+        let dimming = TWINKLE_DEPTH * (wave + 1.) / 2.;
+        sprite.color = Color::WHITE.with_alpha(LAYERS[layer_index.0].brightness * (1. - dimming));
+    }
 }
 
 fn set_background_color(mut clear_color: ResMut<ClearColor>) {
@@ -0,0 +1,112 @@
+/// Keeps only the frequencies of an audio source between two cutoffs. Used to split the soundtrack
+/// into stems while it plays, see soundtrack.rs
+use bevy::audio::Source;
+use std::f32::consts::{FRAC_1_SQRT_2, PI};
+use std::time::Duration;
+
+/// A second order Butterworth filter, from the Audio EQ Cookbook
+#[derive(Clone, Copy, Default)]
+struct Biquad {
+    b0: f32,
+    b1: f32,
+    b2: f32,
+    a1: f32,
+    a2: f32,
+    x1: f32,
+    x2: f32,
+    y1: f32,
+    y2: f32,
+}
+
+impl Biquad {
+    fn new(cutoff: u32, sample_rate: u32, high_pass: bool) -> Self {
+        // Cutoffs past the Nyquist frequency make the filter unstable
+        let cutoff = (cutoff as f32).min(sample_rate as f32 * 0.49);
+        let w0 = 2. * PI * cutoff / sample_rate as f32;
+        let (sin, cos) = w0.sin_cos();
+        let alpha = sin / (2. * FRAC_1_SQRT_2);
+        let a0 = 1. + alpha;
+
+        let (b0, b1) = if high_pass {
+            ((1. + cos) / 2., -(1. + cos))
+        } else {
+            ((1. - cos) / 2., 1. - cos)
+        };
+
+        Self {
+            b0: b0 / a0,
+            b1: b1 / a0,
+            b2: b0 / a0,
+            a1: -2. * cos / a0,
+            a2: (1. - alpha) / a0,
+            ..Self::default()
+        }
+    }
+
+    fn filter(&mut self, x: f32) -> f32 {
+        let y = self.b0 * x + self.b1 * self.x1 + self.b2 * self.x2
+            - self.a1 * self.y1
+            - self.a2 * self.y2;
+        (self.x2, self.x1) = (self.x1, x);
+        (self.y2, self.y1) = (self.y1, y);
+        y
+    }
+}
+
+/// A high and a low pass filter for each channel. Samples are interleaved, so every channel has to
+/// keep its own history
+pub struct BandPass<S> {
+    input: S,
+    /// The high and low pass filters of each channel
+    filters: Vec<[Biquad; 2]>,
+    /// The channel the next sample belongs to
+    channel: usize,
+}
+
+impl<S: Source<Item = f32>> BandPass<S> {
+    /// Keeps the frequencies of `input` from `low` to `high`, in Hz
+    pub fn new(input: S, low: u32, high: u32) -> Self {
+        let sample_rate = input.sample_rate();
+        let channel_filters = [
+            Biquad::new(low, sample_rate, true),
+            Biquad::new(high, sample_rate, false),
+        ];
+
+        Self {
+            filters: vec![channel_filters; input.channels().max(1) as usize],
+            input,
+            channel: 0,
+        }
+    }
+}
+
+impl<S: Source<Item = f32>> Iterator for BandPass<S> {
+    type Item = f32;
+
+    fn next(&mut self) -> Option<f32> {
+        let sample = self.input.next()?;
+        let channel = self.channel;
+        self.channel = (channel + 1) % self.filters.len();
+
+        let [high_pass, low_pass] = &mut self.filters[channel];
+        Some(low_pass.filter(high_pass.filter(sample)))
+    }
+}
+
+impl<S: Source<Item = f32>> Source for BandPass<S> {
+    fn current_frame_len(&self) -> Option<usize> {
+        self.input.current_frame_len()
+    }
+
+    fn channels(&self) -> u16 {
+        self.input.channels()
+    }
+
+    fn sample_rate(&self) -> u32 {
+        self.input.sample_rate()
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        self.input.total_duration()
+    }
+}
@@ -0,0 +1,278 @@
+/// Fading afterimages behind boops and a burst of particles whenever a boop passes a hoop. Both
+/// reuse their sprites instead of spawning new ones, and the EffectsQuality setting scales them
+/// down, or turns them off, for slower machines
+use crate::game_error::notify;
+use crate::gameplay_events::BoopPassedHoop;
+use crate::hoops_boops_loops::{
+    Boop, Loop, Orbit, PreviousLoopPosition, hoop_position, streak_color,
+};
+use bevy::prelude::*;
+use std::f32::consts::PI;
+use std::time::Duration;
+
+/// Afterimages per boop at High quality
+const MAX_TRAIL_LENGTH: usize = 6;
+/// Radians between afterimages
+const TRAIL_SPACING: f32 = 0.035;
+const TRAIL_ALPHA: f32 = 0.35;
+
+/// Particles alive at once at most, bursts past this are skipped
+const MAX_PARTICLES: usize = 256;
+const PARTICLE_LIFETIME: Duration = Duration::from_millis(400);
+/// In pixels per second
+const PARTICLE_SPEED: f32 = 120.;
+const PARTICLE_SCALE: f32 = 0.03;
+
+#[derive(Resource, Default, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum EffectsQuality {
+    Off,
+    Low,
+    #[default]
+    High,
+}
+
+impl EffectsQuality {
+    fn trail_length(self) -> usize {
+        match self {
+            EffectsQuality::Off => 0,
+            EffectsQuality::Low => MAX_TRAIL_LENGTH / 2,
+            EffectsQuality::High => MAX_TRAIL_LENGTH,
+        }
+    }
+
+    fn particles_per_burst(self) -> usize {
+        match self {
+            EffectsQuality::Off => 0,
+            EffectsQuality::Low => 3,
+            EffectsQuality::High => 6,
+        }
+    }
+
+    fn next(self) -> Self {
+        match self {
+            EffectsQuality::Off => EffectsQuality::Low,
+            EffectsQuality::Low => EffectsQuality::High,
+            EffectsQuality::High => EffectsQuality::Off,
+        }
+    }
+}
+
+/// Marker struct for an afterimage
+#[derive(Component)]
+struct Afterimage;
+
+/// A boop's afterimages, closest first. They're children of the loop, like the boop
+#[derive(Component)]
+struct Afterimages([Entity; MAX_TRAIL_LENGTH]);
+
+/// Part of a hoop pass burst, hidden while it's in the pool
+#[derive(Component)]
+pub struct Particle {
+    velocity: Vec2,
+    timer: Timer,
+}
+
+/// Hidden particles ready to be reused
+#[derive(Resource, Default)]
+struct ParticlePool {
+    free: Vec<Entity>,
+    spawned: usize,
+}
+
+pub fn plugin(app: &mut App) {
+    app.init_resource::<EffectsQuality>()
+        .init_resource::<ParticlePool>()
+        .add_systems(
+            Update,
+            (
+                cycle_quality_on_hotkey,
+                spawn_afterimages,
+                update_afterimages,
+                update_particles,
+            )
+                .chain(),
+        )
+        .add_observer(burst_on_boop_passed_hoop)
+        .add_observer(despawn_afterimages);
+}
+
+/// E cycles through the qualities
+fn cycle_quality_on_hotkey(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut quality: ResMut<EffectsQuality>,
+) {
+    if keyboard.just_pressed(KeyCode::KeyE) {
+        *quality = quality.next();
+        notify(format!("Effects quality: {:?}", *quality));
+    }
+}
+
+/// Every boop gets its afterimages once, they're hidden instead of despawned at lower qualities
+fn spawn_afterimages(
+    boop_q: Query<(Entity, &Sprite, &ChildOf), Added<Boop>>,
+    mut commands: Commands,
+) {
+    for (boop, sprite, child_of) in boop_q {
+        let afterimages = [(); MAX_TRAIL_LENGTH].map(|_| {
+            commands
+                .spawn((
+                    Afterimage,
+                    Sprite::from_image(sprite.image.clone()),
+                    Transform::default(),
+                    Visibility::Hidden,
+                    Pickable::IGNORE,
+                    ChildOf(child_of.parent()),
+                ))
+                .id()
+        });
+        commands.entity(boop).insert(Afterimages(afterimages));
+    }
+}
+
+/// Afterimages aren't the boop's children, so they're despawned along with it here
+fn despawn_afterimages(
+    t: Trigger<OnRemove, Boop>,
+    boop_q: Query<&Afterimages>,
+    mut commands: Commands,
+) {
+    if let Ok(afterimages) = boop_q.get(t.target()) {
+        for afterimage in afterimages.0 {
+            // Already gone if the whole loop was despawned
+            commands.entity(afterimage).try_despawn();
+        }
+    }
+}
+
+/// Places afterimages along the orbit behind their boop, fading out and colored by its streak.
+/// There are thousands of them late in the game, so the orbit and color are worked out once per
+/// boop, and afterimages are only written to when something about them changed
+fn update_afterimages(
+    boop_q: Query<(&Boop, &Orbit, &PreviousLoopPosition, &Afterimages)>,
+    mut afterimage_q: Query<(&mut Transform, &mut Sprite, &mut Visibility), With<Afterimage>>,
+    quality: Res<EffectsQuality>,
+    fixed_time: Res<Time<Fixed>>,
+) {
+    let trail_length = quality.trail_length();
+    let overstep_fraction = fixed_time.overstep_fraction();
+
+    for (boop, orbit, previous, afterimages) in boop_q {
+        let loop_position = orbit.interpolated_loop_position(previous, overstep_fraction);
+        let color = streak_color(boop.streak());
+
+        for (index, afterimage) in afterimages.0.iter().enumerate() {
+            // They're spawned hidden, so the ones past the trail only need hiding when it shortens
+            if index >= trail_length && !quality.is_changed() {
+                break;
+            }
+            let Ok((mut transform, mut sprite, mut visibility)) = afterimage_q.get_mut(*afterimage)
+            else {
+                continue;
+            };
+
+            if index >= trail_length {
+                visibility.set_if_neq(Visibility::Hidden);
+                continue;
+            }
+            visibility.set_if_neq(Visibility::Inherited);
+
+            let behind = (index + 1) as f32;
+            let fade = 1. - index as f32 / trail_length as f32;
+
+            let mut drawn = orbit.starting_transform;
+            drawn.scale *= 1. - 0.08 * behind;
+            drawn.translation.z -= 0.1;
+            drawn.rotate_around(
+                Vec3::ZERO,
+                Quat::from_rotation_z(loop_position - TRAIL_SPACING * behind),
+            );
+            transform.set_if_neq(drawn);
+
+            let faded = color.with_alpha(TRAIL_ALPHA * fade);
+            if sprite.color != faded {
+                sprite.color = faded;
+            }
+        }
+    }
+}
+
+fn burst_on_boop_passed_hoop(
+    t: Trigger<BoopPassedHoop>,
+    loop_q: Query<(&Loop, &GlobalTransform)>,
+    mut particle_q: Query<(&mut Particle, &mut Transform, &mut Sprite, &mut Visibility)>,
+    mut pool: ResMut<ParticlePool>,
+    quality: Res<EffectsQuality>,
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+) {
+    let Ok((r#loop, loop_transform)) = loop_q.get(t.r#loop) else {
+        return;
+    };
+
+    let origin = loop_transform.transform_point(hoop_position(t.hoop_index).extend(3.));
+    let color = r#loop.planet().get_hoop_color();
+    let count = quality.particles_per_burst();
+
+    for i in 0..count {
+        // Evenly spread, turned a little per hoop so bursts don't all look the same
+        let angle = 2. * PI * i as f32 / count as f32 + t.hoop_index as f32;
+        let particle = Particle {
+            velocity: Vec2::from_angle(angle) * PARTICLE_SPEED,
+            timer: Timer::new(PARTICLE_LIFETIME, TimerMode::Once),
+        };
+        let transform = Transform {
+            translation: origin,
+            scale: Vec3::splat(PARTICLE_SCALE),
+            ..default()
+        };
+
+        if let Some(entity) = pool.free.pop() {
+            if let Ok((mut p, mut tr, mut sprite, mut visibility)) = particle_q.get_mut(entity) {
+                *p = particle;
+                *tr = transform;
+                sprite.color = color;
+                *visibility = Visibility::Visible;
+            }
+        } else if pool.spawned < MAX_PARTICLES {
+            pool.spawned += 1;
+            commands.spawn((
+                particle,
+                transform,
+                Sprite {
+                    image: asset_server.load("boop.png"),
+                    color,
+                    ..default()
+                },
+                Visibility::Visible,
+                Pickable::IGNORE,
+            ));
+        }
+    }
+}
+
+/// Moves and fades particles, putting them back in the pool once they're done
+fn update_particles(
+    particle_q: Query<(
+        Entity,
+        &mut Particle,
+        &mut Transform,
+        &mut Sprite,
+        &mut Visibility,
+    )>,
+    mut pool: ResMut<ParticlePool>,
+    time: Res<Time>,
+) {
+    for (entity, mut particle, mut transform, mut sprite, mut visibility) in particle_q {
+        if *visibility == Visibility::Hidden {
+            continue;
+        }
+
+        if particle.timer.tick(time.delta()).finished() {
+            *visibility = Visibility::Hidden;
+            pool.free.push(entity);
+            continue;
+        }
+
+        transform.translation += (particle.velocity * time.delta_secs()).extend(0.);
+        sprite.color = sprite.color.with_alpha(particle.timer.fraction_remaining());
+    }
+}
@@ -1,7 +1,15 @@
-use crate::hoops_boops_loops::{AddBoop, AddHoop, Orbit, Planet};
-use crate::loot::Loot;
+use crate::affordability::PriceTag;
+use crate::focus::{Focusable, Focused, Press, press_on_click};
+use crate::game_error::{GameError, GameResult};
+use crate::gameplay_events::{PurchaseKind, Purchased};
+use crate::hoops_boops_loops::{
+    AddBoop, AddHoop, Loop, MAX_BOOP_SPRITES, MAX_HOOPS, Orbit, Planet, loot_per_second,
+};
+use crate::prices::BOOP_PRICE_GROWTH;
 use crate::scales::*;
-use bevy::audio::PlaybackMode;
+use crate::spatial_audio::SPATIAL_DESPAWN;
+use crate::tooltip::{Hovered, Tooltip, hide_tooltip, show_tooltip};
+use crate::wallet::{Funds, Price};
 use bevy::color::palettes::basic::BLACK;
 use bevy::prelude::*;
 use bevy_tweening::Animator;
@@ -16,22 +24,49 @@ use std::time::Duration;
 #[derive(Component)]
 struct MoonBtn {
     /// Goes to next price after buying,
-    price_list: Vec<i32>,
+    price_list: Vec<Price>,
+    /// Prices past the end of price_list multiply the last one by this for every buy. None stops
+    /// buying at the end of the list
+    price_growth: Option<f32>,
     /// corresponds to an index in price_list
     current_price_index: usize,
     /// The text that displays the price
     text: Entity,
+    /// The text under it that displays the currencies besides loot the price needs, if any
+    extras_text: Entity,
     /// The loop that this btn buys for
     r#loop: Entity,
+    /// Buys when pressed while this btn or its loop is focused
+    hotkey: KeyCode,
+    kind: PurchaseKind,
 }
 
 impl MoonBtn {
     fn reached_max_buy_amount(&self) -> bool {
-        return (self.price_list.len() - 1) == self.current_price_index;
+        return self.price_growth.is_none()
+            && (self.price_list.len() - 1) == self.current_price_index;
     }
 
-    fn get_current_price(&self) -> i32 {
-        return self.price_list[self.current_price_index];
+    fn get_current_price(&self) -> Price {
+        self.get_price(self.current_price_index)
+    }
+
+    /// The price after the current one, None if the current one is the last buy
+    fn get_next_price(&self) -> Option<Price> {
+        let next = self.current_price_index + 1;
+        let last_buy = self.price_growth.is_none() && next >= self.price_list.len() - 1;
+        (!last_buy).then(|| self.get_price(next))
+    }
+
+    fn get_price(&self, index: usize) -> Price {
+        if let Some(price) = self.price_list.get(index) {
+            return *price;
+        }
+
+        let last_price = self.price_list.last().copied().unwrap_or(Price::loot(1));
+        let growth = self.price_growth.unwrap_or(1.) as f64;
+        let past_list = (index + 1 - self.price_list.len()) as i32;
+        last_price.scaled(growth.powi(past_list))
     }
 }
 
@@ -46,22 +81,30 @@ const BUY_BOOP_MARGIN_TO_PLANET_WHEN_ZOOMED_OUT: f32 = 73.;
 pub const BUY_BOOP_STARTING_HEIGHT: f32 =
     PLANET_FILE_RADIUS + (BUY_BOOP_MARGIN_TO_PLANET_WHEN_ZOOMED_OUT / ZOOMED_OUT_PLANET_SCALE);
 
+const MOON_BTN_FOCUS_RADIUS: f32 = 65.;
+
 pub fn buy_boops_and_hoops_plugin(app: &mut App) {
-    app.add_systems(FixedUpdate, advance_moon_btn_orbits);
+    app.add_systems(FixedUpdate, advance_moon_btn_orbits)
+        .add_systems(Update, (buy_on_hotkey, update_moon_btn_tooltips))
+        .add_observer(play_successful_buy_sound);
 }
 
-/// Creates a moon button that buys boops
+/// Creates a moon button that buys boops, prices keep growing by BOOP_PRICE_GROWTH past
+/// `boop_prices`
 pub fn create_buy_boop_button(
     r#loop: Entity,
-    boop_prices: [i32; 5],
+    boop_prices: [Price; 5],
     mut commands: &mut Commands,
     asset_server: &AssetServer,
 ) -> Entity {
     create_buy_btn::<AddBoop>(
         r#loop,
         boop_prices.to_vec(),
+        Some(BOOP_PRICE_GROWTH),
         &"buy-boop-showcase.png",
         BUY_BOOP_STARTING_ORBIT,
+        KeyCode::Digit1,
+        PurchaseKind::Boop,
         &mut commands,
         &asset_server,
         Transform {
@@ -75,15 +118,18 @@ pub fn create_buy_boop_button(
 pub fn create_buy_hoop_button(
     r#loop: Entity,
     planet: Planet,
-    hoop_prices: [i32; 8],
+    hoop_prices: [Price; 8],
     mut commands: &mut Commands,
     asset_server: &AssetServer,
 ) -> Entity {
     create_buy_btn::<AddHoop>(
         r#loop,
         hoop_prices.to_vec(),
+        None,
         &planet.get_hoop_showcase_path(),
         BUY_BOOP_STARTING_ORBIT + PI,
+        KeyCode::Digit2,
+        PurchaseKind::Hoop,
         &mut commands,
         &asset_server,
         Transform {
@@ -107,13 +153,19 @@ fn advance_moon_btn_orbits(btns: Query<&mut Orbit, With<MoonBtn>>, time: Res<Tim
 /// Creates a buy moon btn.
 ///
 /// \param T The command that is triggered on Buy
+/// \param price_growth see MoonBtn::price_growth
 /// \param showcase_path the path to the image on top of the moon
 /// \param starting_loop_position see Orbit::current_loop_position
-fn create_buy_btn<T: Command>(
+/// \param hotkey see MoonBtn::hotkey
+/// \param kind what Purchased reports being bought
+fn create_buy_btn<T: Command<GameResult>>(
     r#loop: Entity,
-    prices: Vec<i32>,
+    prices: Vec<Price>,
+    price_growth: Option<f32>,
     showcase_path: &str,
     starting_loop_position: f32,
+    hotkey: KeyCode,
+    kind: PurchaseKind,
     commands: &mut Commands,
     asset_server: &AssetServer,
     showcase_transform: Transform,
@@ -128,7 +180,7 @@ where
 
     let buy_btn = commands
         .spawn((
-            Sprite::from_image(moon_img),
+            Sprite::from_image(moon_img.clone()),
             Orbit {
                 current_loop_position: starting_loop_position,
                 starting_transform: Transform {
@@ -137,6 +189,13 @@ where
                 },
             },
             Pickable::default(),
+            Focusable::new(MOON_BTN_FOCUS_RADIUS),
+            Tooltip::default(),
+            PriceTag {
+                price: Some(prices[0]),
+                shape: moon_img,
+                ring_radius: Some(MOON_BTN_FOCUS_RADIUS + 15.),
+            },
         ))
         .id();
 
@@ -169,9 +228,9 @@ where
 
     let text = commands
         .spawn((
-            Text2d::new(i32_to_display_str(prices[0])),
+            Text2d::new(i32_to_display_str(prices[0].loot)),
             TextFont {
-                font: spacey_font,
+                font: spacey_font.clone(),
                 font_size: 40.,
                 ..default()
             },
@@ -187,60 +246,128 @@ where
         ))
         .id();
 
+    let extras_text = commands
+        .spawn((
+            Text2d::new(prices[0].extras_label()),
+            TextFont {
+                font: spacey_font,
+                font_size: 24.,
+                ..default()
+            },
+            TextColor(extras_color(&prices[0])),
+            Transform {
+                translation: Vec3::new(0., -35., 0.),
+                ..default()
+            },
+            Pickable {
+                should_block_lower: false,
+                is_hoverable: false,
+            },
+        ))
+        .id();
+
     commands
         .entity(buy_btn)
-        .add_children(&[showcase, loot_symbol, text])
+        .add_children(&[showcase, loot_symbol, text, extras_text])
         .insert(MoonBtn {
             price_list: prices,
+            price_growth,
             current_price_index: 0,
             text,
+            extras_text,
             r#loop,
+            hotkey,
+            kind,
         })
-        .observe(buy_new_x_on_click::<T>);
+        .observe(press_on_click)
+        .observe(buy_new_x_on_press::<T>)
+        .observe(show_tooltip_on_hover)
+        .observe(hide_tooltip_on_out);
 
     commands.entity(r#loop).add_child(buy_btn);
 
     buy_btn
 }
 
-// If enough loot, decrements loot, queues the T command which adds the new thing bought, and updates the price text. Otherwise, makes a little *err* sound and turns orange briefly.
-fn buy_new_x_on_click<T: Command>(
-    trigger: Trigger<Pointer<Click>>,
-    mut loot: ResMut<Loot>,
+/// Presses the moon btn matching the number key pressed on the focused loop, or the loop of the
+/// focused moon btn
+fn buy_on_hotkey(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    focused: Res<Focused>,
+    moon_btn_q: Query<(&MoonBtn, Entity)>,
+    mut commands: Commands,
+) {
+    let Some(focused) = **focused else {
+        return;
+    };
+
+    let focused_loop = moon_btn_q
+        .get(focused)
+        .map(|(moon_btn, _)| moon_btn.r#loop)
+        .unwrap_or(focused);
+
+    for (moon_btn, moon_btn_e) in moon_btn_q {
+        if moon_btn.r#loop == focused_loop && keyboard.just_pressed(moon_btn.hotkey) {
+            commands.trigger_targets(Press, moon_btn_e);
+        }
+    }
+}
+
+// If enough loot and other currencies, spends them, queues the T command which adds the new thing bought, and updates the price text. Otherwise, makes a little *err* sound and turns orange briefly.
+fn buy_new_x_on_press<T: Command<GameResult>>(
+    trigger: Trigger<Press>,
+    mut funds: Funds,
     mut moon_btn_q: Query<(&mut MoonBtn, Entity)>,
+    mut tag_q: Query<&mut PriceTag>,
+    transform_q: Query<&Transform>,
     mut commands: Commands,
     asset_server: Res<AssetServer>,
-) where
+) -> Result
+where
     T: From<Entity>,
 {
-    let (mut moon_btn, moon_btn_e) = moon_btn_q.get_mut(trigger.target).unwrap();
+    let (mut moon_btn, moon_btn_e) = moon_btn_q
+        .get_mut(trigger.target())
+        .map_err(|_| GameError::MissingEntity(trigger.target()))?;
+    let position = transform_q
+        .get(moon_btn.r#loop)
+        .map(|transform| transform.translation)
+        .unwrap_or_default();
 
     if moon_btn.reached_max_buy_amount() {
-        unsuccessful_buy_animation_and_sound(moon_btn_e, &mut commands, &asset_server);
-        return;
+        unsuccessful_buy_animation_and_sound(moon_btn_e, position, &mut commands, &asset_server);
+        return Ok(());
     }
 
-    let enough_loot = **loot >= moon_btn.get_current_price();
-    if enough_loot {
-        **loot -= moon_btn.get_current_price();
+    let price = moon_btn.get_current_price();
+    if funds.spend(&price) {
         moon_btn.current_price_index += 1;
 
-        commands.spawn((
-            AudioPlayer::new(asset_server.load("successful-buy.ogg")),
-            PlaybackSettings {
-                mode: PlaybackMode::Despawn,
-                ..default()
-            },
-        ));
         commands.queue(T::from(moon_btn.r#loop));
+        commands.trigger(Purchased {
+            kind: moon_btn.kind,
+            r#loop: moon_btn.r#loop,
+            price,
+        });
+
+        let new_price = (!moon_btn.reached_max_buy_amount()).then(|| moon_btn.get_current_price());
+        if let Ok(mut tag) = tag_q.get_mut(moon_btn_e) {
+            tag.price = new_price;
+        }
 
-        if !moon_btn.reached_max_buy_amount() {
-            let new_price = moon_btn.get_current_price();
+        if let Some(new_price) = new_price {
             commands
                 .entity(moon_btn.text)
-                .insert(Text2d::new(i32_to_display_str(new_price)));
+                .insert(Text2d::new(i32_to_display_str(new_price.loot)));
+            commands.entity(moon_btn.extras_text).insert((
+                Text2d::new(new_price.extras_label()),
+                TextColor(extras_color(&new_price)),
+            ));
         } else {
             commands.entity(moon_btn.text).insert(Text2d::new("-"));
+            commands
+                .entity(moon_btn.extras_text)
+                .insert(Text2d::default());
         }
 
         let fade_in_blue_tween = Tween::new(
@@ -258,65 +385,138 @@ fn buy_new_x_on_click<T: Command>(
             .entity(moon_btn_e)
             .insert(Animator::new(fade_in_blue_tween));
     } else {
-        unsuccessful_buy_animation_and_sound(moon_btn_e, &mut commands, &asset_server);
+        unsuccessful_buy_animation_and_sound(moon_btn_e, position, &mut commands, &asset_server);
     }
 
-    fn unsuccessful_buy_animation_and_sound(
-        moon_btn_e: Entity,
-        commands: &mut Commands,
-        asset_server: &AssetServer,
-    ) {
-        let fade_in_orange_tween = Tween::new(
-            EaseFunction::QuadraticInOut,
-            Duration::from_secs_f32(0.5),
-            SpriteColorLens {
-                start: Color::WHITE,
-                end: Srgba::rgb(1.0, 0.604, 0.259).into(),
-            },
-        )
-        .with_repeat_count(RepeatCount::Finite(2))
-        .with_repeat_strategy(RepeatStrategy::MirroredRepeat);
+    Ok(())
+}
 
-        commands.spawn((
-            AudioPlayer::new(asset_server.load("unsuccessful-buy.ogg")),
-            PlaybackSettings {
-                mode: PlaybackMode::Despawn,
-                ..default()
-            },
-        ));
+/// Turns the btn orange briefly and makes a little *err* sound, panned to `position` like the
+/// successful buy sound is
+fn unsuccessful_buy_animation_and_sound(
+    moon_btn_e: Entity,
+    position: Vec3,
+    commands: &mut Commands,
+    asset_server: &AssetServer,
+) {
+    let fade_in_orange_tween = Tween::new(
+        EaseFunction::QuadraticInOut,
+        Duration::from_secs_f32(0.5),
+        SpriteColorLens {
+            start: Color::WHITE,
+            end: Srgba::rgb(1.0, 0.604, 0.259).into(),
+        },
+    )
+    .with_repeat_count(RepeatCount::Finite(2))
+    .with_repeat_strategy(RepeatStrategy::MirroredRepeat);
 
-        commands
-            .entity(moon_btn_e)
-            .insert(Animator::new(fade_in_orange_tween));
-    }
+    commands.spawn((
+        AudioPlayer::new(asset_server.load("unsuccessful-buy.ogg")),
+        Transform::from_translation(position),
+        SPATIAL_DESPAWN,
+    ));
+
+    commands
+        .entity(moon_btn_e)
+        .insert(Animator::new(fade_in_orange_tween));
 }
 
-/// Converts i32 to a string to be displayed on the moon btns for price. Only supports whole single digits and whole tens, hundreds, and thousands up to 99000. Will panic otherwise.
+fn show_tooltip_on_hover(t: Trigger<Pointer<Over>>, mut hovered: ResMut<Hovered>) {
+    show_tooltip(t.target(), &mut hovered);
+}
+
+fn hide_tooltip_on_out(t: Trigger<Pointer<Out>>, mut hovered: ResMut<Hovered>) {
+    hide_tooltip(t.target(), &mut hovered);
+}
+
+/// Explains what the hovered btn buys, with how many its loop has, the loot per second buying one
+/// adds and what the one after costs. Only the hovered btn's tooltip is shown, so only it is kept
+/// up to date
+fn update_moon_btn_tooltips(
+    hovered: Res<Hovered>,
+    mut moon_btn_q: Query<(&MoonBtn, &mut Tooltip)>,
+    loop_q: Query<&Loop>,
+) {
+    let Some((moon_btn, mut tooltip)) = hovered.and_then(|entity| moon_btn_q.get_mut(entity).ok())
+    else {
+        return;
+    };
+    let Ok(r#loop) = loop_q.get(moon_btn.r#loop) else {
+        return;
+    };
+    let (boops, hoops) = (r#loop.boop_count(), r#loop.hoop_count());
+    let current_rate = loot_per_second(boops, hoops);
+
+    let (count, explanation, gain) = match moon_btn.kind {
+        PurchaseKind::Hoop => (
+            format!("Hoops {hoops}/{MAX_HOOPS}"),
+            "Every boop earns loot passing through it".to_string(),
+            loot_per_second(boops, hoops + 1) - current_rate,
+        ),
+        _ => (
+            format!("Boops {boops}, no limit"),
+            format!("Earns loot through every hoop. Past {MAX_BOOP_SPRITES} they join the swarm"),
+            loot_per_second(boops + 1, hoops) - current_rate,
+        ),
+    };
+    let text = if moon_btn.reached_max_buy_amount() {
+        format!("{count}\n{explanation}\nAll bought")
+    } else {
+        let next = match moon_btn.get_next_price() {
+            Some(price) => format!("Then {price}"),
+            None => "Last one".to_string(),
+        };
+        format!("{count}\n{explanation}\n+{gain:.1} loot/s\n{next}")
+    };
+
+    tooltip.set_if_neq(Tooltip(text));
+}
+
+/// Panned to the loop bought for. Loops are never parented, so their Transform is already in
+/// world space, even on the tick a new loop is spawned
+fn play_successful_buy_sound(
+    t: Trigger<Purchased>,
+    transform_q: Query<&Transform>,
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+) {
+    let position = transform_q
+        .get(t.r#loop)
+        .map(|transform| transform.translation)
+        .unwrap_or_default();
+
+    commands.spawn((
+        AudioPlayer::new(asset_server.load("successful-buy.ogg")),
+        Transform::from_translation(position),
+        SPATIAL_DESPAWN,
+    ));
+}
+
+/// The color of the first currency besides loot the price needs
+fn extras_color(price: &Price) -> Color {
+    price
+        .extras()
+        .next()
+        .map(|(currency, _)| currency.color())
+        .unwrap_or(BLACK.into())
+}
+
+/// Converts i32 to a string to be displayed on the moon btns for price. Only abbreviates whole single digits and whole tens, hundreds, and thousands up to 99000, anything else is shown as is.
 /// Uses h and k for abbreviations of 100 and 1000 respectively.
 fn i32_to_display_str(num: i32) -> String {
     if num > 99000 {
-        panic!("Tried to display too big of a number");
+        return num.to_string();
     }
 
-    if num >= 1000 {
-        if (num % 1000) != 0 {
-            panic!("Not a whole number!");
-        }
+    if num >= 1000 && (num % 1000) == 0 {
         return " ".to_string() + &(num / 1000).to_string() + "k";
     }
 
-    if num >= 100 {
-        if (num % 100) != 0 {
-            panic!("Not a whole number!");
-        }
+    if num >= 100 && (num % 100) == 0 {
         return " ".to_string() + &(num / 100).to_string() + "h";
     }
 
-    if num >= 10 {
-        if (num % 10) != 0 {
-            panic!("Not a whole number!");
-        }
-
+    if num >= 10 && (num % 10) == 0 {
         return " ".to_string() + &num.to_string();
     }
 
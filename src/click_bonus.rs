@@ -0,0 +1,176 @@
+/// Something to do while idle income comes in. Clicking a planet gives a little loot with a ripple,
+/// clicking a boop speeds it up for a moment. Clicking in a steady rhythm builds a combo that
+/// multiplies the planet bonus
+mod tweens;
+
+use crate::click_bonus::tweens::*;
+use crate::focus::Press;
+use crate::hoops_boops_loops::{Boop, IncomeHalted, Loop, SpeedBurst};
+use crate::loot::Loot;
+use crate::replay::ReplayClock;
+use bevy::prelude::*;
+use bevy_tweening::Animator;
+use std::time::Duration;
+
+/// Loot for clicking a planet, times the combo
+const PLANET_CLICK_BONUS: i32 = 1;
+
+const BOOP_BURST_MULTIPLIER: f32 = 2.5;
+const BOOP_BURST_DURATION: Duration = Duration::from_secs(1);
+
+const MAX_COMBO: u32 = 10;
+/// In fixed ticks, a slower click breaks the combo. Ticks are used instead of real time so
+/// replays get the same combos
+const MAX_CLICK_GAP: u64 = 96;
+/// In fixed ticks, how far a click can be off the rhythm of the last two and still count
+const RHYTHM_TOLERANCE: u64 = 6;
+
+#[derive(Resource, Default)]
+pub struct ComboMeter {
+    combo: u32,
+    last_click: Option<u64>,
+    /// Ticks between the last two clicks
+    rhythm: Option<u64>,
+}
+
+impl ComboMeter {
+    pub fn combo(&self) -> u32 {
+        self.combo
+    }
+
+    /// Counts a click on `tick`, growing the combo if it's in rhythm with the last ones
+    fn click(&mut self, tick: u64) {
+        let gap = self.last_click.map(|last| tick - last);
+        let in_rhythm = match (gap, self.rhythm) {
+            (Some(gap), _) if gap > MAX_CLICK_GAP => false,
+            (Some(gap), Some(rhythm)) => gap.abs_diff(rhythm) <= RHYTHM_TOLERANCE,
+            // The second click sets the rhythm
+            (Some(_), None) => true,
+            (None, _) => false,
+        };
+
+        self.combo = if in_rhythm {
+            (self.combo + 1).min(MAX_COMBO)
+        } else {
+            1
+        };
+        self.rhythm = gap.filter(|gap| *gap <= MAX_CLICK_GAP);
+        self.last_click = Some(tick);
+    }
+}
+
+/// Marker struct for the combo Text
+#[derive(Component)]
+struct ComboText;
+
+/// Despawned when the timer finishes
+#[derive(Component)]
+struct Ripple(Timer);
+
+pub fn plugin(app: &mut App) {
+    app.init_resource::<ComboMeter>()
+        .add_systems(Startup, spawn_combo_text)
+        .add_systems(FixedUpdate, (break_stale_combo, despawn_finished_ripples))
+        .add_systems(
+            Update,
+            update_combo_text.run_if(resource_changed::<ComboMeter>),
+        )
+        .add_observer(bonus_on_planet_press)
+        .add_observer(burst_on_boop_press);
+}
+
+/// Eclipsed planets are cleared by clicking instead, see galaxy_events.rs
+fn bonus_on_planet_press(
+    t: Trigger<Press>,
+    loop_q: Query<&Sprite, (With<Loop>, Without<IncomeHalted>)>,
+    clock: Res<ReplayClock>,
+    mut combo: ResMut<ComboMeter>,
+    mut loot: ResMut<Loot>,
+    mut commands: Commands,
+) {
+    let Ok(sprite) = loop_q.get(t.target()) else {
+        return;
+    };
+
+    combo.click(clock.tick);
+    loot.add(PLANET_CLICK_BONUS * combo.combo as i32);
+
+    let ripple = commands
+        .spawn((
+            Sprite::from_image(sprite.image.clone()),
+            Transform::from_xyz(0., 0., -0.5),
+            Ripple(Timer::new(RIPPLE_DURATION, TimerMode::Once)),
+            Pickable::IGNORE,
+            Animator::new(ripple_grow()),
+            Animator::new(ripple_fade()),
+        ))
+        .id();
+    commands.entity(t.target()).add_child(ripple);
+}
+
+fn burst_on_boop_press(
+    t: Trigger<Press>,
+    boop_q: Query<(), With<Boop>>,
+    clock: Res<ReplayClock>,
+    mut combo: ResMut<ComboMeter>,
+    mut commands: Commands,
+) {
+    if !boop_q.contains(t.target()) {
+        return;
+    }
+
+    combo.click(clock.tick);
+    commands
+        .entity(t.target())
+        .insert(SpeedBurst::new(BOOP_BURST_MULTIPLIER, BOOP_BURST_DURATION));
+}
+
+fn break_stale_combo(mut combo: ResMut<ComboMeter>, clock: Res<ReplayClock>) {
+    let stale = combo
+        .last_click
+        .is_some_and(|last| clock.tick - last > MAX_CLICK_GAP);
+
+    if stale && combo.combo > 0 {
+        *combo = ComboMeter::default();
+    }
+}
+
+fn despawn_finished_ripples(
+    ripple_q: Query<(Entity, &mut Ripple)>,
+    time: Res<Time>,
+    mut commands: Commands,
+) {
+    for (entity, mut ripple) in ripple_q {
+        if ripple.0.tick(time.delta()).finished() {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+fn spawn_combo_text(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.spawn((
+        ComboText,
+        Text::default(),
+        TextFont {
+            font: asset_server.load("SpaceGrotesk-Light.ttf"),
+            font_size: 32.,
+            ..default()
+        },
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(95.),
+            right: Val::Px(55.),
+            ..default()
+        },
+        Pickable::IGNORE,
+    ));
+}
+
+/// Only shown once there's a combo going
+fn update_combo_text(combo: Res<ComboMeter>, mut text: Single<&mut Text, With<ComboText>>) {
+    text.0 = if combo.combo >= 2 {
+        format!("x{} combo", combo.combo)
+    } else {
+        String::new()
+    };
+}
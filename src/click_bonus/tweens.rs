@@ -0,0 +1,28 @@
+use bevy::prelude::*;
+use bevy_tweening::lens::*;
+use bevy_tweening::*;
+use std::time::Duration;
+
+pub const RIPPLE_DURATION: Duration = Duration::from_millis(600);
+
+pub fn ripple_grow() -> Tween<Transform> {
+    Tween::new(
+        EaseFunction::QuadraticOut,
+        RIPPLE_DURATION,
+        TransformScaleLens {
+            start: Vec3::ONE,
+            end: Vec3::splat(1.3),
+        },
+    )
+}
+
+pub fn ripple_fade() -> Tween<Sprite> {
+    Tween::new(
+        EaseFunction::QuadraticOut,
+        RIPPLE_DURATION,
+        SpriteColorLens {
+            start: Color::WHITE.with_alpha(0.5),
+            end: Color::WHITE.with_alpha(0.),
+        },
+    )
+}
@@ -0,0 +1,314 @@
+/// Debug overlay and cheat console for tuning, only built with `--features dev`. F1 toggles it.
+/// Cheats go through the same commands as the game does, like AddBoop and AddHoop
+use crate::background::StarfieldSettings;
+use crate::focus::Press;
+use crate::hoops_boops_loops::{AddBoop, AddHoop, Loop, MAX_HOOPS, Planet};
+use crate::locked_planets::LockedPlanet;
+use crate::loot::Loot;
+use crate::replay::RecordingPaused;
+use crate::transition_to_all_planets::Transitioned;
+use crate::transition_to_first_planet::{FirstPlanet, TransitionToFirstPlanet};
+use crate::wallet::{Price, Wallet};
+use bevy::diagnostic::{DiagnosticsStore, FrameTimeDiagnosticsPlugin};
+use bevy::ecs::entity::Entities;
+use bevy::input::ButtonState;
+use bevy::input::keyboard::{Key, KeyboardInput};
+use bevy::prelude::*;
+
+const HELP: &str = "loot add <n> | boop add <planet> [n] | hoop max <planet> | unlock all | \
+                    timescale <speed> | goto galaxy | stars <density>";
+
+/// How many lines of console output to keep
+const LOG_LENGTH: usize = 8;
+
+#[derive(Resource, Default)]
+struct DevConsole {
+    visible: bool,
+    input: String,
+    log: Vec<String>,
+}
+
+/// Marker struct for the root of the overlay
+#[derive(Component)]
+struct DevOverlay;
+
+/// Marker struct for the Text showing fps, entity counts and such
+#[derive(Component)]
+struct DevStatsText;
+
+/// Marker struct for the Text showing the console log and input
+#[derive(Component)]
+struct DevConsoleText;
+
+/// A line typed into the console, ran with World access once commands are applied
+struct RunConsoleCommand(String);
+impl Command for RunConsoleCommand {
+    fn apply(self, world: &mut World) {
+        let output = match run(world, &self.0) {
+            Ok(output) => output,
+            Err(error) => format!("error: {error}"),
+        };
+
+        let mut console = world.resource_mut::<DevConsole>();
+        console.log.push("> ".to_string() + &self.0);
+        console.log.push(output);
+        let overflow = console.log.len().saturating_sub(LOG_LENGTH);
+        console.log.drain(..overflow);
+    }
+}
+
+pub fn plugin(app: &mut App) {
+    if !app.is_plugin_added::<FrameTimeDiagnosticsPlugin>() {
+        app.add_plugins(FrameTimeDiagnosticsPlugin::default());
+    }
+
+    app.init_resource::<DevConsole>()
+        .add_systems(Startup, spawn_overlay)
+        .add_systems(PreUpdate, type_in_console.after(bevy::input::InputSystem))
+        .add_systems(Update, (update_stats_text, update_console_text));
+}
+
+fn spawn_overlay(mut commands: Commands) {
+    commands
+        .spawn((
+            DevOverlay,
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(10.),
+                left: Val::Px(10.),
+                padding: UiRect::all(Val::Px(10.)),
+                row_gap: Val::Px(10.),
+                flex_direction: FlexDirection::Column,
+                ..default()
+            },
+            BackgroundColor(Color::BLACK.with_alpha(0.8)),
+            Visibility::Hidden,
+            Pickable::IGNORE,
+        ))
+        .with_children(|overlay| {
+            overlay.spawn((DevStatsText, Text::default(), TextFont::from_font_size(16.)));
+            overlay.spawn((
+                DevConsoleText,
+                Text::default(),
+                TextFont::from_font_size(16.),
+            ));
+        });
+}
+
+/// F1 toggles the overlay. While it's open, typing goes to the console instead of the game
+fn type_in_console(
+    mut key_evs: EventReader<KeyboardInput>,
+    mut keyboard: ResMut<ButtonInput<KeyCode>>,
+    mut console: ResMut<DevConsole>,
+    mut overlay: Single<&mut Visibility, With<DevOverlay>>,
+    mut commands: Commands,
+) {
+    for ev in key_evs.read() {
+        if ev.state != ButtonState::Pressed {
+            continue;
+        }
+
+        if ev.key_code == KeyCode::F1 {
+            console.visible = !console.visible;
+            **overlay = if console.visible {
+                Visibility::Visible
+            } else {
+                Visibility::Hidden
+            };
+            continue;
+        }
+
+        if !console.visible {
+            continue;
+        }
+
+        match &ev.logical_key {
+            Key::Enter => {
+                let line = std::mem::take(&mut console.input);
+                if !line.trim().is_empty() {
+                    commands.queue(RunConsoleCommand(line));
+                }
+            }
+            Key::Backspace => {
+                console.input.pop();
+            }
+            _ => {
+                if let Some(text) = &ev.text {
+                    console
+                        .input
+                        .extend(text.chars().filter(|c| !c.is_control()));
+                }
+            }
+        }
+    }
+
+    // Keep keys like Enter and the number hotkeys from also reaching the game
+    if console.visible {
+        keyboard.reset_all();
+    }
+}
+
+fn update_stats_text(
+    console: Res<DevConsole>,
+    diagnostics: Res<DiagnosticsStore>,
+    entities: &Entities,
+    loop_q: Query<&Loop>,
+    transitioned: Res<Transitioned>,
+    mut text: Single<&mut Text, With<DevStatsText>>,
+) {
+    if !console.visible {
+        return;
+    }
+
+    let fps = diagnostics
+        .get(&FrameTimeDiagnosticsPlugin::FPS)
+        .and_then(|fps| fps.smoothed())
+        .unwrap_or_default();
+
+    let mut stats = format!(
+        "fps: {fps:.0}\nentities: {}\ntransitioned to galaxy: {}\n",
+        entities.len(),
+        **transitioned
+    );
+
+    let mut loops: Vec<&Loop> = loop_q.iter().collect();
+    loops.sort_by_key(|r#loop| r#loop.planet().get_number());
+    for r#loop in loops {
+        stats += &format!(
+            "planet {}: {} boops, {} hoops\n",
+            r#loop.planet().get_number(),
+            r#loop.boop_count(),
+            r#loop.hoop_count()
+        );
+    }
+
+    text.0 = stats;
+}
+
+fn update_console_text(
+    console: Res<DevConsole>,
+    mut text: Single<&mut Text, With<DevConsoleText>>,
+) {
+    if !console.is_changed() {
+        return;
+    }
+
+    text.0 = HELP.to_string() + "\n" + &console.log.join("\n") + "\n> " + &console.input + "_";
+}
+
+fn run(world: &mut World, line: &str) -> Result<String, String> {
+    let words: Vec<&str> = line.split_whitespace().collect();
+
+    match words.as_slice() {
+        ["loot", "add", amount] => {
+            let amount: i32 = amount.parse().map_err(|_| "not a number")?;
+            world.resource_mut::<Loot>().add(amount);
+            Ok(format!("added {amount} loot"))
+        }
+        ["boop", "add", planet] => {
+            let r#loop = find_loop(world, parse_planet(planet)?)?;
+            AddBoop(r#loop).apply(world).map_err(|e| e.to_string())?;
+            Ok("added a boop".to_string())
+        }
+        ["boop", "add", planet, amount] => {
+            let amount: u32 = amount.parse().map_err(|_| "not a number")?;
+            let r#loop = find_loop(world, parse_planet(planet)?)?;
+            for _ in 0..amount {
+                AddBoop(r#loop).apply(world).map_err(|e| e.to_string())?;
+            }
+            Ok(format!("added {amount} boops"))
+        }
+        ["hoop", "max", planet] => {
+            let r#loop = find_loop(world, parse_planet(planet)?)?;
+            max_hoops(world, r#loop)?;
+            Ok("maxed hoops".to_string())
+        }
+        ["unlock", "all"] => unlock_all(world),
+        ["timescale", speed] => {
+            let speed: f32 = speed.parse().map_err(|_| "not a number")?;
+            world
+                .resource_mut::<Time<Virtual>>()
+                .set_relative_speed(speed);
+            Ok(format!("time is {speed}x"))
+        }
+        ["goto", "galaxy"] => goto_galaxy(world),
+        ["stars", density] => {
+            let density: f32 = density.parse().map_err(|_| "not a number")?;
+            world.resource_mut::<StarfieldSettings>().density = density;
+            Ok(format!("{density} stars per screen"))
+        }
+        _ => Err(format!("unknown command, try {HELP}")),
+    }
+}
+
+fn parse_planet(planet: &str) -> Result<Planet, String> {
+    let number: i32 = planet.parse().map_err(|_| "not a planet number")?;
+    Planet::from_i32(number).map_err(|e| e.to_string())
+}
+
+fn find_loop(world: &mut World, planet: Planet) -> Result<Entity, String> {
+    world
+        .query::<(Entity, &Loop)>()
+        .iter(world)
+        .find(|(_, r#loop)| r#loop.planet() == planet)
+        .map(|(entity, _)| entity)
+        .ok_or(format!("planet {} isn't unlocked", planet.get_number()))
+}
+
+fn max_hoops(world: &mut World, r#loop: Entity) -> Result<(), String> {
+    let hoop_count = world
+        .get::<Loop>(r#loop)
+        .map(|r#loop| r#loop.hoop_count())
+        .unwrap_or(MAX_HOOPS);
+
+    for _ in hoop_count..MAX_HOOPS {
+        AddHoop(r#loop).apply(world).map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+/// Buys every locked planet as if the player had just enough loot and currencies and clicked on it.
+/// The clicks are left out of the replay along with the cheat
+fn unlock_all(world: &mut World) -> Result<String, String> {
+    let locked_planets: Vec<(Entity, Price)> = world
+        .query::<(Entity, &LockedPlanet)>()
+        .iter(world)
+        .map(|(entity, locked_planet)| (entity, locked_planet.planet().get_price()))
+        .collect();
+
+    if locked_planets.is_empty() {
+        return Err("no locked planets, try goto galaxy first".to_string());
+    }
+
+    world.insert_resource(RecordingPaused);
+    for (locked_planet, price) in &locked_planets {
+        world.resource_mut::<Loot>().add(price.loot);
+        for (currency, amount) in price.extras() {
+            world.resource_mut::<Wallet>().add(currency, amount);
+        }
+        world.trigger_targets(Press, *locked_planet);
+        world.flush();
+    }
+    world.remove_resource::<RecordingPaused>();
+
+    Ok(format!("unlocked {} planets", locked_planets.len()))
+}
+
+/// Skips the titlescreen and first planet by maxing the first planet's hoops
+fn goto_galaxy(world: &mut World) -> Result<String, String> {
+    if **world.resource::<Transitioned>() {
+        return Err("already in the galaxy".to_string());
+    }
+
+    let mut first_planet_q = world.query_filtered::<Entity, With<FirstPlanet>>();
+    if first_planet_q.iter(world).next().is_none() {
+        TransitionToFirstPlanet.apply(world);
+    }
+
+    let first_planet = first_planet_q
+        .single(world)
+        .map_err(|_| "couldn't find the first planet")?;
+    max_hoops(world, first_planet)?;
+
+    Ok("going to the galaxy".to_string())
+}
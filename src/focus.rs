@@ -0,0 +1,193 @@
+/// Lets the whole game be played without a mouse. Arrow keys or a gamepad d-pad move focus between
+/// Focusable entities, Enter or the gamepad A button presses the focused one.
+use crate::screen_size::SCREEN_SIZE;
+use bevy::prelude::*;
+
+/// Marks an entity that can be focused and pressed. The focus ring is drawn as a circle of
+/// `radius` around `offset`, both in the entity's local space so they scale with it
+#[derive(Component, Clone, Copy)]
+pub struct Focusable {
+    pub radius: f32,
+    pub offset: Vec2,
+}
+
+impl Focusable {
+    pub fn new(radius: f32) -> Self {
+        Focusable {
+            radius,
+            offset: Vec2::ZERO,
+        }
+    }
+}
+
+/// Triggered on a Focusable when it is clicked, or when it is focused and Enter or A is pressed.
+/// Observe this instead of Pointer<Click> so interactions work with every input device
+#[derive(Event)]
+pub struct Press;
+
+/// The currently focused entity
+#[derive(Resource, Default, Deref, DerefMut)]
+pub struct Focused(pub Option<Entity>);
+
+const FOCUS_RING_COLOR: Color = Color::srgb(0.333, 0.808, 0.929);
+
+pub fn plugin(app: &mut App) {
+    app.init_resource::<Focused>().add_systems(
+        Update,
+        (clear_focus_on_despawn, move_focus, press_focused).chain(),
+    );
+}
+
+/// Draws the focus ring with gizmos. Separate from the navigation so the game can run without a
+/// renderer
+pub fn focus_ring_plugin(app: &mut App) {
+    app.add_systems(Update, draw_focus_ring.after(press_focused));
+}
+
+/// Observer to add to Focusables so a click focuses and presses them
+pub fn press_on_click(
+    mut t: Trigger<Pointer<Click>>,
+    mut focused: ResMut<Focused>,
+    mut commands: Commands,
+) {
+    // Parents can be Focusable too (moon btns are children of their loop), only press the one
+    // that was clicked
+    t.propagate(false);
+
+    **focused = Some(t.target());
+    commands.trigger_targets(Press, t.target());
+}
+
+fn clear_focus_on_despawn(mut focused: ResMut<Focused>, focusable_q: Query<(), With<Focusable>>) {
+    if focused.is_some_and(|entity| !focusable_q.contains(entity)) {
+        **focused = None;
+    }
+}
+
+/// Moves focus to the closest Focusable in the direction pressed. If nothing is focused, the first
+/// direction pressed focuses the Focusable closest to the center of the screen
+fn move_focus(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    gamepads: Query<&Gamepad>,
+    focusable_q: Query<(
+        Entity,
+        &Focusable,
+        &GlobalTransform,
+        &InheritedVisibility,
+        Option<&ComputedNode>,
+    )>,
+    mut focused: ResMut<Focused>,
+) {
+    let direction = [
+        (KeyCode::ArrowUp, GamepadButton::DPadUp, Vec2::Y),
+        (KeyCode::ArrowDown, GamepadButton::DPadDown, Vec2::NEG_Y),
+        (KeyCode::ArrowLeft, GamepadButton::DPadLeft, Vec2::NEG_X),
+        (KeyCode::ArrowRight, GamepadButton::DPadRight, Vec2::X),
+    ]
+    .into_iter()
+    .find(|(key, button, _)| {
+        keyboard.just_pressed(*key) || gamepads.iter().any(|g| g.just_pressed(*button))
+    })
+    .map(|(_, _, direction)| direction);
+
+    let Some(direction) = direction else {
+        return;
+    };
+
+    let candidates = focusable_q
+        .iter()
+        .filter(|(_, _, _, visibility, _)| visibility.get())
+        // UI nodes with Display::None are still visible, they're just laid out with no size
+        .filter(|(_, _, _, _, node)| node.is_none_or(|node| !node.is_empty()))
+        .map(|(entity, focusable, transform, _, node)| {
+            (entity, focus_center(focusable, transform, node))
+        })
+        // Moon btns wait offscreen before being brought in
+        .filter(|(_, center)| center.abs().cmple(SCREEN_SIZE / 2.).all());
+
+    let current = focused.and_then(|e| focusable_q.get(e).ok());
+    let Some((current, focusable, transform, _, node)) = current else {
+        **focused = candidates
+            .min_by(|a, b| a.1.length().total_cmp(&b.1.length()))
+            .map(|(entity, _)| entity);
+        return;
+    };
+
+    let current_center = focus_center(focusable, transform, node);
+
+    // Only consider Focusables within 60 degrees of the pressed direction, weighting
+    // off-axis ones as further away so moving stays in a straight line when possible
+    **focused = candidates
+        .filter(|(entity, _)| *entity != current)
+        .filter_map(|(entity, center)| {
+            let to = center - current_center;
+            let alignment = to.normalize_or_zero().dot(direction);
+            (alignment > 0.5).then_some((entity, to.length() / alignment))
+        })
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(entity, _)| entity)
+        .or(Some(current));
+}
+
+fn press_focused(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    gamepads: Query<&Gamepad>,
+    focused: Res<Focused>,
+    mut commands: Commands,
+) {
+    let Some(focused) = **focused else {
+        return;
+    };
+
+    let pressed = keyboard.any_just_pressed([KeyCode::Enter, KeyCode::NumpadEnter])
+        || gamepads
+            .iter()
+            .any(|g| g.just_pressed(GamepadButton::South));
+
+    if pressed {
+        commands.trigger_targets(Press, focused);
+    }
+}
+
+fn draw_focus_ring(
+    focused: Res<Focused>,
+    focusable_q: Query<(&Focusable, &GlobalTransform, Option<&ComputedNode>)>,
+    mut gizmos: Gizmos,
+) {
+    let Some((focusable, transform, node)) = focused.and_then(|e| focusable_q.get(e).ok()) else {
+        return;
+    };
+
+    let radius = focusable.radius * transform.scale().x;
+    gizmos
+        .circle_2d(
+            focus_center(focusable, transform, node),
+            radius,
+            FOCUS_RING_COLOR,
+        )
+        .resolution(64);
+}
+
+/// The world position of the center of the focus ring. UI nodes are laid out in physical pixels
+/// from the top left of the screen instead, so they're moved into the world like the camera sees
+/// them
+fn focus_center(
+    focusable: &Focusable,
+    transform: &GlobalTransform,
+    node: Option<&ComputedNode>,
+) -> Vec2 {
+    let center = transform
+        .transform_point(focusable.offset.extend(0.))
+        .truncate();
+
+    match node {
+        Some(node) => {
+            let logical = center * node.inverse_scale_factor();
+            Vec2::new(
+                logical.x - SCREEN_SIZE.x / 2.,
+                SCREEN_SIZE.y / 2. - logical.y,
+            )
+        }
+        None => center,
+    }
+}
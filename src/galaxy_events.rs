@@ -0,0 +1,343 @@
+/// Random events that keep the galaxy from sitting still once every planet is set up. Every so
+/// often one of these happens:
+/// - A comet crosses the screen, catching it gives a loot bonus
+/// - A meteor shower doubles how fast one planet's boops go
+/// - An eclipse dims a planet and halts its income until it's clicked
+///
+/// Everything runs on the fixed timestep and draws from GameRng, so events happen at the same
+/// time and place for the same seed
+use crate::focus::{Focusable, Press, press_on_click};
+use crate::game_error::notify;
+use crate::game_rng::GameRng;
+use crate::gameplay_events::GalaxyEventStarted;
+use crate::hoops_boops_loops::{IncomeHalted, Loop, Planet, SpeedBurst};
+use crate::loot::Loot;
+use crate::replay::PlayerInput;
+use crate::screen_size::SCREEN_SIZE;
+use crate::spatial_audio::SPATIAL_DESPAWN;
+use crate::transition_to_all_planets::Transitioned;
+use bevy::prelude::*;
+use rand::Rng;
+use std::time::Duration;
+
+/// How long after reaching the galaxy the first event happens
+const FIRST_EVENT_DELAY: Duration = Duration::from_secs(45);
+
+/// The time between events is random, in seconds
+const MIN_EVENT_INTERVAL: f32 = 45.;
+const MAX_EVENT_INTERVAL: f32 = 120.;
+
+const COMET_CROSSING_DURATION: Duration = Duration::from_secs(12);
+const COMET_RADIUS: f32 = 40.;
+/// A comet gives a tenth of the player's loot, but at least this much
+const COMET_MIN_BONUS: i32 = 10;
+
+const METEOR_SHOWER_DURATION: Duration = Duration::from_secs(20);
+const METEOR_SHOWER_SPEED_MULTIPLIER: f32 = 2.;
+const METEOR_SHOWER_COLOR: Color = Color::srgb(1.0, 0.604, 0.259);
+
+/// Eclipses clear by themselves if nobody clicks them
+const ECLIPSE_DURATION: Duration = Duration::from_secs(60);
+const ECLIPSE_COLOR: Color = Color::srgb(0.3, 0.3, 0.35);
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum GalaxyEventKind {
+    Comet,
+    MeteorShower,
+    Eclipse,
+}
+
+const KINDS: [GalaxyEventKind; 3] = [
+    GalaxyEventKind::Comet,
+    GalaxyEventKind::MeteorShower,
+    GalaxyEventKind::Eclipse,
+];
+
+/// Counts down to the next event
+#[derive(Resource)]
+struct GalaxyEventTimer(Timer);
+
+impl Default for GalaxyEventTimer {
+    fn default() -> Self {
+        GalaxyEventTimer(Timer::new(FIRST_EVENT_DELAY, TimerMode::Once))
+    }
+}
+
+/// A comet crossing the screen from left to right
+#[derive(Component)]
+struct Comet {
+    timer: Timer,
+    y: f32,
+}
+
+/// On a loop while a meteor shower or eclipse is happening on it, ended by end_planet_events
+#[derive(Component)]
+struct PlanetEvent {
+    kind: GalaxyEventKind,
+    timer: Timer,
+}
+
+pub fn plugin(app: &mut App) {
+    app.init_resource::<GalaxyEventTimer>()
+        .add_systems(
+            FixedUpdate,
+            (
+                start_galaxy_events.run_if(|transitioned: Res<Transitioned>| **transitioned),
+                move_comets,
+                end_planet_events,
+            ),
+        )
+        .add_observer(clear_eclipse_on_press);
+}
+
+fn start_galaxy_events(
+    mut event_timer: ResMut<GalaxyEventTimer>,
+    loop_q: Query<(Entity, &Loop, &Transform), Without<PlanetEvent>>,
+    comet_q: Query<(), With<Comet>>,
+    mut rng: ResMut<GameRng>,
+    time: Res<Time>,
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+) {
+    if !event_timer.0.tick(time.delta()).just_finished() {
+        return;
+    }
+
+    let interval = rng.random_range(MIN_EVENT_INTERVAL..MAX_EVENT_INTERVAL);
+    event_timer.0 = Timer::from_seconds(interval, TimerMode::Once);
+
+    let kind = KINDS[rng.random_range(0..KINDS.len())];
+    let planet = match kind {
+        GalaxyEventKind::Comet => {
+            if !comet_q.is_empty() {
+                return;
+            }
+            let y = rng.random_range(-SCREEN_SIZE.y / 3.0..SCREEN_SIZE.y / 3.);
+            start_comet(y, &mut commands, &asset_server);
+            None
+        }
+        GalaxyEventKind::MeteorShower | GalaxyEventKind::Eclipse => {
+            // Sorted so the same seed picks the same planet
+            let mut loops: Vec<_> = loop_q.iter().collect();
+            loops.sort_by_key(|(_, r#loop, _)| r#loop.planet() as usize);
+            if loops.is_empty() {
+                return;
+            }
+            let (loop_entity, r#loop, transform) = loops[rng.random_range(0..loops.len())];
+            let planet = r#loop.planet();
+
+            if kind == GalaxyEventKind::MeteorShower {
+                start_meteor_shower(loop_entity, planet, transform, &mut commands, &asset_server);
+            } else {
+                start_eclipse(loop_entity, planet, transform, &mut commands, &asset_server);
+            }
+            Some(planet)
+        }
+    };
+
+    commands.trigger(GalaxyEventStarted { kind, planet });
+}
+
+fn start_meteor_shower(
+    r#loop: Entity,
+    planet: Planet,
+    transform: &Transform,
+    commands: &mut Commands,
+    asset_server: &AssetServer,
+) {
+    commands.entity(r#loop).insert((
+        PlanetEvent {
+            kind: GalaxyEventKind::MeteorShower,
+            timer: Timer::new(METEOR_SHOWER_DURATION, TimerMode::Once),
+        },
+        SpeedBurst::new(METEOR_SHOWER_SPEED_MULTIPLIER, METEOR_SHOWER_DURATION),
+    ));
+    set_loop_color(r#loop, METEOR_SHOWER_COLOR, commands);
+    play_sound(
+        "boop-going-through-hoop.ogg",
+        2.,
+        transform.translation,
+        commands,
+        asset_server,
+    );
+    notify(format!(
+        "A meteor shower is speeding up planet {}!",
+        planet.get_number()
+    ));
+}
+
+fn start_eclipse(
+    r#loop: Entity,
+    planet: Planet,
+    transform: &Transform,
+    commands: &mut Commands,
+    asset_server: &AssetServer,
+) {
+    commands.entity(r#loop).insert((
+        PlanetEvent {
+            kind: GalaxyEventKind::Eclipse,
+            timer: Timer::new(ECLIPSE_DURATION, TimerMode::Once),
+        },
+        IncomeHalted,
+    ));
+    set_loop_color(r#loop, ECLIPSE_COLOR, commands);
+    play_sound(
+        "unsuccessful-buy.ogg",
+        0.5,
+        transform.translation,
+        commands,
+        asset_server,
+    );
+    notify(format!(
+        "Planet {} is eclipsed, click it to bring back its loot",
+        planet.get_number()
+    ));
+}
+
+fn start_comet(y: f32, commands: &mut Commands, asset_server: &AssetServer) {
+    let start = Vec3::new(-SCREEN_SIZE.x / 2. - COMET_RADIUS, y, 5.);
+
+    commands
+        .spawn((
+            Comet {
+                timer: Timer::new(COMET_CROSSING_DURATION, TimerMode::Once),
+                y,
+            },
+            Sprite {
+                image: asset_server.load("boop.png"),
+                color: METEOR_SHOWER_COLOR,
+                ..default()
+            },
+            Transform {
+                translation: start,
+                scale: Vec3::splat(0.2),
+                ..default()
+            },
+            Pickable::default(),
+            Focusable::new(COMET_RADIUS),
+            PlayerInput::CatchComet,
+        ))
+        .observe(press_on_click)
+        .observe(catch_comet_on_press);
+
+    play_sound(
+        "boop-going-through-hoop.ogg",
+        0.5,
+        start,
+        commands,
+        asset_server,
+    );
+    notify("A comet! Catch it for loot");
+}
+
+fn move_comets(
+    comet_q: Query<(Entity, &mut Comet, &mut Transform)>,
+    time: Res<Time>,
+    mut commands: Commands,
+) {
+    for (entity, mut comet, mut transform) in comet_q {
+        comet.timer.tick(time.delta());
+
+        let from = -SCREEN_SIZE.x / 2. - COMET_RADIUS;
+        let to = SCREEN_SIZE.x / 2. + COMET_RADIUS;
+        transform.translation.x = from + (to - from) * comet.timer.fraction();
+        transform.translation.y = comet.y;
+
+        if comet.timer.finished() {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+fn catch_comet_on_press(
+    t: Trigger<Press>,
+    transform_q: Query<&Transform, With<Comet>>,
+    mut loot: ResMut<Loot>,
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+) {
+    let Ok(transform) = transform_q.get(t.target()) else {
+        return;
+    };
+
+    let bonus = (**loot / 10).max(COMET_MIN_BONUS);
+    loot.add(bonus);
+
+    play_sound(
+        "successful-buy.ogg",
+        1.,
+        transform.translation,
+        &mut commands,
+        &asset_server,
+    );
+    notify(format!("Caught the comet for {bonus} loot!"));
+    commands.entity(t.target()).despawn();
+}
+
+/// Clicking an eclipsed planet, or pressing it with the keyboard or gamepad, ends the eclipse
+fn clear_eclipse_on_press(
+    t: Trigger<Press>,
+    event_q: Query<(&PlanetEvent, &Transform)>,
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+) {
+    let Ok((event, transform)) = event_q.get(t.target()) else {
+        return;
+    };
+    if event.kind != GalaxyEventKind::Eclipse {
+        return;
+    }
+
+    end_planet_event(t.target(), &mut commands);
+    play_sound(
+        "successful-buy.ogg",
+        1.,
+        transform.translation,
+        &mut commands,
+        &asset_server,
+    );
+}
+
+fn end_planet_events(
+    event_q: Query<(Entity, &mut PlanetEvent)>,
+    time: Res<Time>,
+    mut commands: Commands,
+) {
+    for (entity, mut event) in event_q {
+        if event.timer.tick(time.delta()).finished() {
+            end_planet_event(entity, &mut commands);
+        }
+    }
+}
+
+/// SpeedBurst ends by itself at the same time as a meteor shower
+fn end_planet_event(r#loop: Entity, commands: &mut Commands) {
+    commands
+        .entity(r#loop)
+        .remove::<(PlanetEvent, IncomeHalted)>();
+    set_loop_color(r#loop, Color::WHITE, commands);
+}
+
+fn set_loop_color(r#loop: Entity, color: Color, commands: &mut Commands) {
+    commands
+        .entity(r#loop)
+        .entry::<Sprite>()
+        .and_modify(move |mut sprite| sprite.color = color);
+}
+
+fn play_sound(
+    path: &'static str,
+    speed: f32,
+    position: Vec3,
+    commands: &mut Commands,
+    asset_server: &AssetServer,
+) {
+    commands.spawn((
+        AudioPlayer::new(asset_server.load(path)),
+        Transform::from_translation(position),
+        PlaybackSettings {
+            speed,
+            ..SPATIAL_DESPAWN
+        },
+    ));
+}
@@ -0,0 +1,175 @@
+/// Recoverable gameplay errors. Instead of panicking they are logged and shown as a notice at the
+/// bottom of the screen, so a bad entity never ends a long idle session
+use bevy::ecs::error::{BevyError, ErrorContext};
+use bevy::prelude::*;
+use std::fmt;
+use std::sync::Mutex;
+use std::time::Duration;
+
+#[derive(Debug)]
+pub enum GameError {
+    /// There are only six planets
+    InvalidPlanet(i32),
+    /// Tried to add a hoop to a loop that already has MAX_HOOPS
+    MaxHoops(Entity),
+    /// The entity was expected to have a Loop component
+    NotALoop(Entity),
+    /// Tried to take the only boop a loop has left
+    LastBoop(Entity),
+    /// The entity was despawned while something still referenced it
+    MissingEntity(Entity),
+    /// Zooming the camera only works with orthographic projections
+    NonOrthographicProjection,
+    /// A replay file couldn't be read, written or played back
+    InvalidReplay(String),
+    /// A save, like the tutorial's, couldn't be read or written
+    InvalidSave(String),
+}
+
+impl fmt::Display for GameError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GameError::InvalidPlanet(i) => write!(f, "There is no planet number {i}"),
+            GameError::MaxHoops(_) => write!(f, "That planet already has all of its hoops"),
+            GameError::NotALoop(e) => write!(f, "{e} is not a planet"),
+            GameError::LastBoop(_) => write!(f, "That planet only has one boop left"),
+            GameError::MissingEntity(e) => write!(f, "{e} disappeared unexpectedly"),
+            GameError::NonOrthographicProjection => {
+                write!(f, "The camera can't zoom with this projection")
+            }
+            GameError::InvalidReplay(reason) => write!(f, "Replay failed: {reason}"),
+            GameError::InvalidSave(reason) => write!(f, "Saving failed: {reason}"),
+        }
+    }
+}
+
+impl std::error::Error for GameError {}
+
+pub type GameResult<T = ()> = Result<T, GameError>;
+
+/// Messages waiting to be shown. The global error handler is a plain fn without World access, so
+/// it leaves them here for show_notices
+static PENDING_NOTICES: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+/// How long a notice stays on screen
+const NOTICE_DURATION: Duration = Duration::from_secs(4);
+
+/// Marker struct for the container the notices stack in
+#[derive(Component)]
+struct NoticeContainer;
+
+/// A notice that is despawned once the timer finishes
+#[derive(Component)]
+struct Notice {
+    timer: Timer,
+    message: String,
+}
+
+pub fn plugin(app: &mut App) {
+    app.add_systems(Startup, spawn_notice_container)
+        .add_systems(Update, (show_notices, despawn_finished_notices));
+}
+
+/// Set as Bevy's GLOBAL_ERROR_HANDLER. Logs every error from systems, observers and commands as a
+/// warning and shows a notice instead of panicking
+pub fn handle_error(error: BevyError, ctx: ErrorContext) {
+    let message = match error.downcast_ref::<GameError>() {
+        Some(game_error) => game_error.to_string(),
+        None => "Something went wrong, but the game kept going".to_string(),
+    };
+    queue_notice(message);
+
+    bevy::ecs::error::warn(error, ctx);
+}
+
+/// For places that can't return a Result, like tween lenses
+pub fn report(error: GameError) {
+    warn!("{error}");
+    queue_notice(error.to_string());
+}
+
+/// Shows a notice that isn't an error, like a galaxy event starting
+pub fn notify(message: impl Into<String>) {
+    queue_notice(message.into());
+}
+
+fn queue_notice(message: String) {
+    if let Ok(mut notices) = PENDING_NOTICES.lock() {
+        notices.push(message);
+    }
+}
+
+fn spawn_notice_container(mut commands: Commands) {
+    commands.spawn((
+        NoticeContainer,
+        Node {
+            position_type: PositionType::Absolute,
+            bottom: Val::Px(30.),
+            width: Val::Percent(100.),
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Center,
+            row_gap: Val::Px(10.),
+            ..default()
+        },
+        Pickable::IGNORE,
+    ));
+}
+
+fn show_notices(
+    container: Single<Entity, With<NoticeContainer>>,
+    notice_q: Query<&Notice>,
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+) {
+    let Ok(mut pending) = PENDING_NOTICES.lock() else {
+        return;
+    };
+
+    let mut shown: Vec<String> = notice_q.iter().map(|n| n.message.clone()).collect();
+    for message in pending.drain(..) {
+        // Errors that happen every tick would otherwise flood the screen
+        if shown.contains(&message) {
+            continue;
+        }
+
+        let notice = commands
+            .spawn((
+                Node {
+                    padding: UiRect::axes(Val::Px(20.), Val::Px(8.)),
+                    border: UiRect::all(Val::Px(1.)),
+                    ..default()
+                },
+                BackgroundColor(Color::WHITE.with_alpha(0.1)),
+                BorderColor(Color::BLACK),
+                BorderRadius::MAX,
+                Notice {
+                    timer: Timer::new(NOTICE_DURATION, TimerMode::Once),
+                    message: message.clone(),
+                },
+            ))
+            .with_child((
+                Text::new(message.clone()),
+                TextFont {
+                    font: asset_server.load("SpaceGrotesk-Light.ttf"),
+                    font_size: 24.,
+                    ..default()
+                },
+            ))
+            .id();
+
+        commands.entity(*container).add_child(notice);
+        shown.push(message);
+    }
+}
+
+fn despawn_finished_notices(
+    notice_q: Query<(Entity, &mut Notice)>,
+    time: Res<Time<Real>>,
+    mut commands: Commands,
+) {
+    for (entity, mut notice) in notice_q {
+        if notice.timer.tick(time.delta()).finished() {
+            commands.entity(entity).despawn();
+        }
+    }
+}
@@ -0,0 +1,38 @@
+/// All randomness in the game goes through GameRng so a session can be reproduced from its seed,
+/// see replay.rs
+use bevy::prelude::*;
+use rand::rngs::StdRng;
+use rand::{RngCore, SeedableRng};
+
+/// Use this instead of rand::rng() anywhere gameplay needs randomness
+#[derive(Resource, Deref, DerefMut)]
+pub struct GameRng {
+    seed: u64,
+    #[deref]
+    rng: StdRng,
+}
+
+impl GameRng {
+    pub fn from_seed(seed: u64) -> Self {
+        GameRng {
+            seed,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    /// The seed this rng started with
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+}
+
+/// Seeds from entropy, to be reproducible insert a GameRng::from_seed instead
+impl Default for GameRng {
+    fn default() -> Self {
+        GameRng::from_seed(rand::rng().next_u64())
+    }
+}
+
+pub fn plugin(app: &mut App) {
+    app.init_resource::<GameRng>();
+}
@@ -0,0 +1,186 @@
+/// Lets the player fast forward once they've earned it. 2x unlocks on reaching the galaxy and 4x
+/// once every planet is unlocked. Only Time<Virtual> speeds up, so FixedUpdate runs more often with
+/// the same step
+use crate::focus::{Focusable, Press, press_on_click};
+use crate::locked_planets::LockedPlanet;
+use crate::transition_to_all_planets::Transitioned;
+use bevy::prelude::*;
+
+/// Every speed the player can pick, slowest first
+pub const SPEEDS: [f32; 3] = [1., 2., 4.];
+
+/// Roughly half a speed btn's width
+const SPEED_BTN_FOCUS_RADIUS: f32 = 32.;
+
+const SELECTED_COLOR: Color = Color::WHITE;
+const UNSELECTED_COLOR: Color = Color::BLACK;
+
+/// Indexes into SPEEDS
+#[derive(Resource, Default)]
+pub struct GameSpeed {
+    unlocked: usize,
+    selected: usize,
+}
+
+impl GameSpeed {
+    pub fn speed(&self) -> f32 {
+        SPEEDS[self.selected]
+    }
+
+    /// How many speeds past 1x are unlocked
+    pub fn unlocked(&self) -> usize {
+        self.unlocked
+    }
+
+    /// Selects `speed` if it's unlocked, returning whether it was
+    pub fn select(&mut self, speed: f32) -> bool {
+        match SPEEDS[..=self.unlocked].iter().position(|s| *s == speed) {
+            Some(i) => {
+                self.selected = i;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Goes to the next unlocked speed, wrapping back to 1x
+    pub fn cycle(&mut self) {
+        self.selected = (self.selected + 1) % (self.unlocked + 1);
+    }
+}
+
+/// Marker struct for the row of speed buttons
+#[derive(Component)]
+struct SpeedBtns;
+
+/// A button selecting SPEEDS[i]
+#[derive(Component)]
+pub struct SpeedBtn(pub usize);
+
+pub fn plugin(app: &mut App) {
+    app.init_resource::<GameSpeed>()
+        .add_systems(Startup, spawn_speed_btns)
+        .add_systems(
+            Update,
+            (
+                unlock_speeds,
+                cycle_speed_on_hotkey,
+                (set_virtual_speed, update_speed_btns).run_if(resource_changed::<GameSpeed>),
+            )
+                .chain(),
+        );
+}
+
+fn unlock_speeds(
+    transitioned: Res<Transitioned>,
+    locked_planet_q: Query<(), With<LockedPlanet>>,
+    mut speed: ResMut<GameSpeed>,
+) {
+    if !**transitioned {
+        return;
+    }
+
+    // Locked planets are spawned on the same sync point Transitioned is set
+    let target = if locked_planet_q.is_empty() { 2 } else { 1 };
+    if speed.unlocked < target {
+        speed.unlocked = target;
+    }
+}
+
+/// F or the gamepad's north button cycles through the unlocked speeds
+fn cycle_speed_on_hotkey(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    gamepad_q: Query<&Gamepad>,
+    mut speed: ResMut<GameSpeed>,
+) {
+    let pressed = keyboard.just_pressed(KeyCode::KeyF)
+        || gamepad_q
+            .iter()
+            .any(|gamepad| gamepad.just_pressed(GamepadButton::North));
+
+    if pressed && speed.unlocked > 0 {
+        speed.cycle();
+    }
+}
+
+fn set_virtual_speed(speed: Res<GameSpeed>, mut time: ResMut<Time<Virtual>>) {
+    time.set_relative_speed(speed.speed());
+}
+
+fn spawn_speed_btns(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let font = asset_server.load("SpaceGrotesk-Light.ttf");
+
+    commands
+        .spawn((
+            SpeedBtns,
+            Node {
+                position_type: PositionType::Absolute,
+                bottom: Val::Px(30.),
+                right: Val::Px(30.),
+                column_gap: Val::Px(10.),
+                ..default()
+            },
+            Visibility::Hidden,
+            Pickable::IGNORE,
+        ))
+        .with_children(|row| {
+            for (i, speed) in SPEEDS.iter().enumerate() {
+                row.spawn((
+                    SpeedBtn(i),
+                    Focusable::new(SPEED_BTN_FOCUS_RADIUS),
+                    Node {
+                        padding: UiRect::axes(Val::Px(14.), Val::Px(6.)),
+                        border: UiRect::all(Val::Px(1.)),
+                        ..default()
+                    },
+                    BackgroundColor(Color::WHITE.with_alpha(0.1)),
+                    BorderColor(UNSELECTED_COLOR),
+                    BorderRadius::MAX,
+                ))
+                .with_child((
+                    Text::new(format!("{speed}x")),
+                    TextFont {
+                        font: font.clone(),
+                        font_size: 24.,
+                        ..default()
+                    },
+                    Pickable::IGNORE,
+                ))
+                .observe(press_on_click)
+                .observe(select_on_press);
+            }
+        });
+}
+
+fn select_on_press(t: Trigger<Press>, btn_q: Query<&SpeedBtn>, mut speed: ResMut<GameSpeed>) {
+    if let Ok(btn) = btn_q.get(t.target()) {
+        speed.select(SPEEDS[btn.0]);
+    }
+}
+
+/// Shows the buttons once there's something to pick, hiding the locked ones and outlining the
+/// selected one
+fn update_speed_btns(
+    speed: Res<GameSpeed>,
+    mut row: Single<&mut Visibility, With<SpeedBtns>>,
+    btn_q: Query<(&SpeedBtn, &mut Node, &mut BorderColor)>,
+) {
+    **row = if speed.unlocked > 0 {
+        Visibility::Inherited
+    } else {
+        Visibility::Hidden
+    };
+
+    for (btn, mut node, mut border) in btn_q {
+        node.display = if btn.0 <= speed.unlocked {
+            Display::Flex
+        } else {
+            Display::None
+        };
+        border.0 = if btn.0 == speed.selected {
+            SELECTED_COLOR
+        } else {
+            UNSELECTED_COLOR
+        };
+    }
+}
@@ -0,0 +1,89 @@
+/// Events for the things gameplay does that other features react to. They're triggered globally,
+/// so visuals, audio, stats and such each add their own observer instead of being called from the
+/// systems that trigger them
+use crate::galaxy_events::GalaxyEventKind;
+use crate::hoops_boops_loops::Planet;
+use crate::wallet::Price;
+use bevy::prelude::*;
+
+/// Triggered once for every hoop a boop passes through, even several in one tick
+#[derive(Event, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct BoopPassedHoop {
+    pub r#loop: Entity,
+    /// Index in Loop::hoop_sprites
+    pub hoop_index: usize,
+    pub boop: Entity,
+    /// Loot earned by the pass
+    pub payout: i32,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PurchaseKind {
+    Boop,
+    Hoop,
+    /// A locked planet, see PlanetUnlocked
+    Loop,
+    /// A lane between two planets, see transfer_lanes.rs
+    Lane,
+}
+
+/// Triggered after the price was paid, once the purchase has been queued
+#[derive(Event, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Purchased {
+    pub kind: PurchaseKind,
+    /// The loop bought for, or the new loop when buying a locked planet
+    pub r#loop: Entity,
+    pub price: Price,
+}
+
+/// Triggered at most once a tick for each loop whose swarm, its boops past MAX_BOOP_SPRITES, passed
+/// through hoops. They're counted in bulk instead of one BoopPassedHoop each
+#[derive(Event, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct SwarmPassedHoops {
+    pub r#loop: Entity,
+    pub passes: u64,
+    /// Loot earned by the passes
+    pub payout: i32,
+}
+
+/// Triggered when a boop passes through every hoop of a lap in a row, after the last
+/// BoopPassedHoop
+#[derive(Event, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct PerfectLoop {
+    pub r#loop: Entity,
+    pub boop: Entity,
+    /// Extra loot on top of the hoop payouts
+    pub bonus: i32,
+}
+
+/// Triggered when a boop arrives at the end of a lane, before it's added to its new loop
+#[derive(Event, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct BoopTransferred {
+    pub from: Planet,
+    pub to: Planet,
+    /// Loot earned by making it
+    pub bonus: i32,
+}
+
+/// Triggered when a locked planet is bought, after Purchased
+#[derive(Event, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct PlanetUnlocked {
+    pub planet: Planet,
+}
+
+/// Triggered when the current quest is done, before the next one starts. See quests.rs
+#[derive(Event, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct QuestCompleted {
+    /// Index in QUESTS
+    pub quest: usize,
+    /// Loot earned by it
+    pub reward: i32,
+}
+
+/// Triggered when a random galaxy event starts
+#[derive(Event, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct GalaxyEventStarted {
+    pub kind: GalaxyEventKind,
+    /// The planet it happens on, comets don't have one
+    pub planet: Option<Planet>,
+}
@@ -0,0 +1,117 @@
+/// Helpers for running the game without a window, renderer or audio device. Used by the integration
+/// tests in tests/ to drive the ECS directly
+use crate::focus::Press;
+use crate::hoops_boops_loops::{AddHoop, BOOP_SPEED, LoopInfo, MAX_HOOPS, Planet, spawn_loop};
+use crate::loot::Loot;
+use crate::prices::*;
+use crate::replay::PlayerInput;
+use crate::transition_to_first_planet::{FirstPlanet, TransitionToFirstPlanet};
+use bevy::input::InputPlugin;
+use bevy::prelude::*;
+use bevy::time::TimeUpdateStrategy;
+use std::f32::consts::PI;
+
+/// Each update moves the clock forward by exactly one fixed tick
+pub use crate::replay::TICK;
+
+/// Time for a boop to go all the way around its loop
+pub const LAP_SECONDS: f32 = 2. * PI / BOOP_SPEED;
+
+/// Builds an App with MinimalPlugins, AssetPlugin and the game plugins. Time only moves forward by
+/// TICK on each update, see advance_seconds. Startup has already run when this returns
+pub fn headless_app() -> App {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, AssetPlugin::default(), InputPlugin))
+        // Normally registered by the rendering, text and audio plugins. Handles to them are still
+        // created by the game, the assets just never finish loading
+        .init_asset::<Image>()
+        .init_asset::<Font>()
+        .init_asset::<AudioSource>()
+        .init_asset::<ColorMaterial>()
+        .init_resource::<ClearColor>()
+        .insert_resource(TimeUpdateStrategy::ManualDuration(TICK))
+        .add_plugins(crate::plugin);
+
+    app.finish();
+    app.cleanup();
+    app.update();
+
+    app
+}
+
+/// Runs enough updates to move the clock forward by `seconds`
+pub fn advance_seconds(app: &mut App, seconds: f32) {
+    let ticks = (seconds / TICK.as_secs_f32()).round() as u32;
+    for _ in 0..ticks {
+        app.update();
+    }
+}
+
+/// Spawns a loop at the origin with the first planet's prices, returning the loop, buy boop btn,
+/// and buy hoop btn, in that order. Like every loop it starts with one boop and one hoop
+pub fn spawn_test_loop(app: &mut App, planet: Planet) -> (Entity, Entity, Entity) {
+    let world = app.world_mut();
+    let asset_server = world.resource::<AssetServer>().clone();
+
+    let spawned = spawn_loop(
+        LoopInfo {
+            position: Vec2::ZERO,
+            planet,
+            boop_prices: FIRST_PLANET_BOOP_PRICES,
+            hoop_prices: FIRST_PLANET_HOOP_PRICES,
+        },
+        &mut world.commands(),
+        &asset_server,
+    );
+    world.flush();
+
+    spawned
+}
+
+/// Leaves the titlescreen and buys every hoop of the first planet, which starts the zoom out to the
+/// galaxy. Returns the first planet's loop
+pub fn transition_to_galaxy(app: &mut App) -> Entity {
+    apply(app, TransitionToFirstPlanet);
+    let first_planet = app
+        .world_mut()
+        .query_filtered::<Entity, With<FirstPlanet>>()
+        .single(app.world())
+        .expect("The first planet should have been spawned");
+
+    for _ in 1..MAX_HOOPS {
+        apply(app, AddHoop(first_planet)).expect("The first planet has room for every hoop");
+    }
+    first_planet
+}
+
+/// Presses whatever `input` is on, like a click would. Panics if nothing has it
+pub fn press(app: &mut App, input: PlayerInput) {
+    let world = app.world_mut();
+    let entity = world
+        .query::<(Entity, &PlayerInput)>()
+        .iter(world)
+        .find(|(_, i)| **i == input)
+        .map(|(entity, _)| entity)
+        .expect("Nothing to press for that input");
+
+    world.trigger_targets(Press, entity);
+    world.flush();
+}
+
+/// Applies a command right away, like Commands::queue would on the next sync point
+pub fn apply<C: Command<T>, T>(app: &mut App, command: C) -> T {
+    command.apply(app.world_mut())
+}
+
+/// The Loot held
+pub fn loot(app: &App) -> i32 {
+    **app.world().resource::<Loot>()
+}
+
+/// Counts the entities with component T
+pub fn count<T: Component>(app: &mut App) -> usize {
+    app.world_mut()
+        .query_filtered::<(), With<T>>()
+        .iter(app.world())
+        .count()
+}
@@ -1,9 +1,12 @@
 /// This module handles the core logic of each Loop. Note that a "r#" had to be prepended when using
 /// loop because its a keyword
 use crate::buy_boops_and_hoops::{create_buy_boop_button, create_buy_hoop_button};
-use crate::loot::Loot;
-use crate::play_hoop_through_boop_sounds::PlayBoopThroughHoop;
+use crate::focus::{Focusable, press_on_click};
+use crate::game_error::{GameError, GameResult, report};
+use crate::gameplay_events::{BoopPassedHoop, PerfectLoop, SwarmPassedHoops};
 use crate::prices::PLANET_PRICES;
+use crate::replay::PlayerInput;
+use crate::wallet::Price;
 use bevy::prelude::*;
 use bevy_tweening::Animator;
 use bevy_tweening::RepeatCount;
@@ -13,36 +16,84 @@ use bevy_tweening::lens::SpriteColorLens;
 use std::f32::consts::PI;
 use std::time::Duration;
 
+/// The sprite of a boop. Gameplay happens on its loop's BoopOrbits, this and its Orbit only mirror
+/// them for visuals
 #[derive(Component)]
-struct Boop {
-    in_hoop: bool,
+pub struct Boop {
+    /// How many hoops in a row it passed through without passing an empty hoop slot
+    streak: u32,
+}
+
+impl Boop {
+    pub fn streak(&self) -> u32 {
+        self.streak
+    }
 }
 
 /// Rotates entities in an orbit around their Transform origin (which could be their parents)
 /// from `starting_transform`
-/// REMEMBER, the transform will be reset the starting_transform on each tick
+/// REMEMBER, the transform will be reset the starting_transform on each frame
 #[derive(Component)]
+#[require(PreviousLoopPosition)]
 pub struct Orbit {
     /// In Radians from 0 to 2*PI
     pub current_loop_position: f32,
     pub starting_transform: Transform, // Will determine how far away the entitiy will be
 }
 
-impl Default for Boop {
-    fn default() -> Self {
-        Boop { in_hoop: false }
+impl Orbit {
+    /// Where the orbit is drawn between the last two fixed ticks, `overstep_fraction` of the way
+    /// from the previous Orbit::current_loop_position to the current one
+    pub fn interpolated_loop_position(
+        &self,
+        previous: &PreviousLoopPosition,
+        overstep_fraction: f32,
+    ) -> f32 {
+        let Some(previous) = previous.0 else {
+            return self.current_loop_position;
+        };
+
+        // The shortest way around, current_loop_position wraps back to 0 after 2PI
+        let travelled = (self.current_loop_position - previous + PI).rem_euclid(2. * PI) - PI;
+        (previous + travelled * overstep_fraction).rem_euclid(2. * PI)
     }
 }
 
+/// Orbit::current_loop_position before the last fixed tick moved it, used to draw orbits smoothly
+/// between ticks. None until the orbit's first tick
+#[derive(Component, Default)]
+pub struct PreviousLoopPosition(Option<f32>);
+
 #[derive(Component)]
 struct Hoop {}
 
+/// Multiplies how fast a boop goes, or every boop of a loop when put on a loop, until the timer
+/// finishes
+#[derive(Component)]
+pub struct SpeedBurst {
+    pub multiplier: f32,
+    pub timer: Timer,
+}
+
+impl SpeedBurst {
+    pub fn new(multiplier: f32, duration: Duration) -> Self {
+        SpeedBurst {
+            multiplier,
+            timer: Timer::new(duration, TimerMode::Once),
+        }
+    }
+}
+
+/// Put on a loop to stop its hoop passes paying out any loot
+#[derive(Component)]
+pub struct IncomeHalted;
+
 /// Keeps track of when a hoop is bought, is used to transition to all planets on all hoops bought
 #[derive(Event)]
 pub struct AllHoopsBought;
 
 /// Corresponds to the different planet sprites since each hoop is colored to their specific planet
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
 pub enum Planet {
     One,
     Two,
@@ -53,20 +104,20 @@ pub enum Planet {
 }
 
 impl Planet {
-    /// There are only six planets, any number not 1 through 6 will panic
-    pub fn from_i32(i: i32) -> Self {
+    /// There are only six planets, any number not 1 through 6 is an error
+    pub fn from_i32(i: i32) -> GameResult<Self> {
         match i {
-            1 => Planet::One,
-            2 => Planet::Two,
-            3 => Planet::Three,
-            4 => Planet::Four,
-            5 => Planet::Five,
-            6 => Planet::Six,
-            _ => panic!("Tried to convert non 1-6 i32 to planet"),
+            1 => Ok(Planet::One),
+            2 => Ok(Planet::Two),
+            3 => Ok(Planet::Three),
+            4 => Ok(Planet::Four),
+            5 => Ok(Planet::Five),
+            6 => Ok(Planet::Six),
+            _ => Err(GameError::InvalidPlanet(i)),
         }
     }
 
-    fn get_number(&self) -> String {
+    pub fn get_number(&self) -> String {
         match self {
             Planet::One => "1".to_string(),
             Planet::Two => "2".to_string(),
@@ -99,6 +150,18 @@ impl Planet {
             + &".png";
     }
 
+    /// The color the hoop sprites of this planet are drawn in
+    pub fn get_hoop_color(&self) -> Color {
+        match self {
+            Planet::One => Color::srgb(0.766, 0.76, 0.76),
+            Planet::Two => Color::srgb(0.999, 0.822, 0.287),
+            Planet::Three => Color::srgb(0.564, 0.911, 0.999),
+            Planet::Four => Color::srgb(0.486, 0.555, 1.0),
+            Planet::Five => Color::srgb(0.876, 0.24, 0.24),
+            Planet::Six => Color::srgb(1.0, 0.579, 0.24),
+        }
+    }
+
     /// We need a different hoop showcase for each planet because they are colored for the
     /// individual planet
     pub fn get_hoop_showcase_path(&self) -> String {
@@ -106,7 +169,7 @@ impl Planet {
         return "buy-hoop-showcase/".to_string() + &number + &".png";
     }
 
-    pub fn get_price(&self) -> i32 {
+    pub fn get_price(&self) -> Price {
         match self {
             Planet::One => PLANET_PRICES[0],
             Planet::Two => PLANET_PRICES[1],
@@ -119,125 +182,442 @@ impl Planet {
 }
 
 #[derive(Component)]
-struct Loop {
+pub struct Loop {
+    /// Boops with their own sprite, at most MAX_BOOP_SPRITES
     boops: Vec<Entity>,
+    orbits: BoopOrbits,
+    swarm: BoopSwarm,
     hoop_count: i32,
     /// The hoop_sprites.0 is outer and hoop_sprites.1 is inner
     hoop_sprites: Vec<(Entity, Entity)>,
     planet: Planet,
 }
 
+/// Every boop of a loop, one entry each in the same order as Loop::boops. Kept as arrays so moving
+/// thousands of boops and finding the hoops they passed is a few tight loops instead of a lookup
+/// per boop
+#[derive(Default)]
+struct BoopOrbits {
+    /// Like Orbit::current_loop_position
+    angles: Vec<f32>,
+    /// Angles before the last tick moved them, hoops from these on for `travelled` were passed
+    previous_angles: Vec<f32>,
+    /// How far each boop went in the last tick, not wrapped to 2PI so whole laps still count
+    travelled: Vec<f32>,
+    /// Multiplier from a SpeedBurst on the boop, 1 without one
+    speeds: Vec<f32>,
+    streaks: Vec<u32>,
+}
+
+impl BoopOrbits {
+    fn push(&mut self) {
+        self.angles.push(0.);
+        self.previous_angles.push(0.);
+        self.travelled.push(0.);
+        self.speeds.push(1.);
+        self.streaks.push(0);
+    }
+
+    fn pop(&mut self) {
+        self.angles.pop();
+        self.previous_angles.pop();
+        self.travelled.pop();
+        self.speeds.pop();
+        self.streaks.pop();
+    }
+
+    fn remove(&mut self, index: usize) {
+        self.angles.remove(index);
+        self.previous_angles.remove(index);
+        self.travelled.remove(index);
+        self.speeds.remove(index);
+        self.streaks.remove(index);
+    }
+}
+
+/// Boops past MAX_BOOP_SPRITES. They're spread evenly around the loop and move together, so their
+/// hoop passes are counted in bulk instead of one at a time, and drawn as a few sprites no matter
+/// how many there are
+#[derive(Default)]
+struct BoopSwarm {
+    count: u64,
+    /// Like Orbit::current_loop_position, for the first of `sprites`
+    angle: f32,
+    /// Hoop passes earned but not paid yet, always less than 1
+    pending_passes: f64,
+    /// Evenly around the loop once count isn't 0
+    sprites: Vec<Entity>,
+}
+
+/// Marker struct for one of a loop's BoopSwarm::sprites
+#[derive(Component)]
+struct SwarmSprite;
+
+impl Loop {
+    /// Only boops with their own sprite, see boop_count
+    pub fn boops(&self) -> &[Entity] {
+        &self.boops
+    }
+
+    /// Every boop, including the ones in its swarm
+    pub fn boop_count(&self) -> u64 {
+        self.boops.len() as u64 + self.swarm.count
+    }
+
+    pub fn hoop_count(&self) -> i32 {
+        self.hoop_count
+    }
+
+    pub fn planet(&self) -> Planet {
+        self.planet
+    }
+}
+
 /// The max number of hoops
-const MAX_HOOPS: i32 = 8;
-const MAX_BOOPS: usize = 16;
+pub const MAX_HOOPS: i32 = 8;
+/// Boops past this many on a loop join its swarm instead of getting their own sprite
+pub const MAX_BOOP_SPRITES: usize = 16;
+/// How many sprites a swarm is drawn with
+const SWARM_SPRITES: usize = 24;
+
+/// In radians per second
+pub const BOOP_SPEED: f32 = 1.2;
+
+/// Roughly the loot a loop earns each second, counting every boop through every hoop once a lap
+/// without streaks, speed bursts or galaxy events
+pub fn loot_per_second(boops: u64, hoops: i32) -> f32 {
+    boops as f32 * hoops as f32 * BOOP_SPEED / (2. * PI)
+}
+
+/// The payout goes up by 1 for every this many hoops in a streak, so once per perfect loop
+const STREAK_PER_MULTIPLIER: u32 = MAX_HOOPS as u32;
+const MAX_STREAK_MULTIPLIER: i32 = 4;
+/// Extra loot for a boop passing through every hoop of a lap in a row
+pub const PERFECT_LOOP_BONUS: i32 = 8;
+
+/// Boops change color as their streak grows, see tint_boops_by_streak
+const STREAK_COLORS: [Color; 3] = [
+    Color::WHITE,
+    Color::srgb(0.333, 0.808, 0.929),
+    Color::srgb(1.0, 0.604, 0.259),
+];
 
 const LOOP_FILE_HEIGHT: f32 = 472.;
 const LOOP_RADIUS: f32 = LOOP_FILE_HEIGHT / 2.;
+const BOOP_TO_LOOP_MARGIN: f32 = 15.;
+
+/// Where the hoops are drawn on the loop sprite, counterclockwise from the top left
+/// Have to convert because GIMP reports coordinates in ui-space
+const HOOP_POSITIONS: [Vec2; 8] = [
+    Vec2::new(-95., 222.),
+    Vec2::new(-225., 91.),
+    Vec2::new(-222., -93.),
+    Vec2::new(-98., -223.),
+    Vec2::new(95., -226.),
+    Vec2::new(221., -96.),
+    Vec2::new(223., 92.),
+    Vec2::new(98., 219.),
+];
 
 pub fn hoops_boops_loops_plugin(app: &mut App) {
-    app.add_systems(
-        FixedUpdate,
-        (move_boops_forward, orbit, get_loot_on_boop_in_hoop).chain(),
-    );
+    app.add_systems(FixedFirst, store_previous_loop_positions)
+        .add_systems(
+            FixedUpdate,
+            (
+                end_speed_bursts,
+                move_boops_forward,
+                trigger_boop_passed_hoop,
+                move_swarms,
+                mirror_boop_orbits,
+            )
+                .chain(),
+        )
+        .add_systems(Update, tint_boops_by_streak)
+        .add_systems(
+            PostUpdate,
+            orbit.before(TransformSystem::TransformPropagate),
+        )
+        .add_observer(flash_passed_hoop)
+        .add_observer(forget_despawned_boop);
 }
 
-/// Positions the transform of an orbit according to Orbit::current_loop_position
-fn orbit(orbit_q: Query<(&mut Transform, &Orbit)>) {
-    for orbit in orbit_q {
-        let mut transform = orbit.0;
-        let orbit = orbit.1;
+/// Runs before anything in FixedUpdate moves an orbit
+fn store_previous_loop_positions(orbit_q: Query<(&Orbit, &mut PreviousLoopPosition)>) {
+    for (orbit, mut previous) in orbit_q {
+        previous.0 = Some(orbit.current_loop_position);
+    }
+}
 
-        *transform = orbit.starting_transform;
-        transform.rotate_around(
-            Vec3::ZERO,
-            Quat::from_rotation_z(orbit.current_loop_position),
-        );
+/// Positions the transform of an orbit according to Orbit::current_loop_position, interpolated
+/// between fixed ticks so orbits move smoothly at any frame rate. Gameplay only ever reads Orbit,
+/// so this doesn't change what happens on each tick
+fn orbit(
+    orbit_q: Query<(&mut Transform, &Orbit, &PreviousLoopPosition)>,
+    fixed_time: Res<Time<Fixed>>,
+) {
+    let overstep_fraction = fixed_time.overstep_fraction();
+
+    for (mut transform, orbit, previous) in orbit_q {
+        let angle = orbit.interpolated_loop_position(previous, overstep_fraction);
+        let start = orbit.starting_transform;
+
+        // Same as rotate_around the origin, without rotating the translation by a quaternion
+        let translation = Vec2::from_angle(angle).rotate(start.translation.truncate());
+        *transform = Transform {
+            translation: translation.extend(start.translation.z),
+            rotation: Quat::from_rotation_z(angle) * start.rotation,
+            scale: start.scale,
+        };
+    }
+}
+
+/// Moves boops forwards by incrementing their angle in BoopOrbits modulating it to keep it between
+/// 0 and 2PI
+fn move_boops_forward(
+    mut loop_q: Query<(&mut Loop, Option<&SpeedBurst>)>,
+    boop_burst_q: Query<(Entity, &ChildOf, &SpeedBurst), With<Boop>>,
+    time: Res<Time>,
+) {
+    for (mut r#loop, _) in &mut loop_q {
+        r#loop.orbits.speeds.fill(1.);
+    }
+    // Only a few boops are ever bursting, so these lookups are cheap
+    for (boop, child_of, burst) in boop_burst_q {
+        let Ok((mut r#loop, _)) = loop_q.get_mut(child_of.parent()) else {
+            continue;
+        };
+        if let Some(i) = r#loop.boops.iter().position(|entity| *entity == boop) {
+            r#loop.orbits.speeds[i] = burst.multiplier;
+        }
+    }
+
+    for (mut r#loop, loop_burst) in &mut loop_q {
+        let loop_multiplier = loop_burst.map_or(1., |burst| burst.multiplier);
+        let orbits = &mut r#loop.orbits;
+        orbits.previous_angles.copy_from_slice(&orbits.angles);
+
+        let boops = orbits.angles.iter_mut().zip(&mut orbits.travelled);
+        for ((angle, travelled), speed) in boops.zip(&orbits.speeds) {
+            let increase = BOOP_SPEED * (speed * loop_multiplier) * time.delta_secs();
+            *travelled = increase;
+            *angle += increase;
+            *angle %= 2. * PI;
+        }
+    }
+}
+
+/// Moves every loop's swarm and triggers SwarmPassedHoops for the whole hoop passes it made. With
+/// its boops spread evenly, each of them passes `hoop_count` hoops a lap, so the passes are worked
+/// out from how far the swarm went. Swarm boops don't have streaks and each pass pays 1
+fn move_swarms(
+    loop_q: Query<(Entity, &mut Loop, Option<&SpeedBurst>, Has<IncomeHalted>)>,
+    time: Res<Time>,
+    mut commands: Commands,
+) {
+    for (loop_entity, mut r#loop, loop_burst, income_halted) in loop_q {
+        if r#loop.swarm.count == 0 {
+            continue;
+        }
+
+        let loop_multiplier = loop_burst.map_or(1., |burst| burst.multiplier);
+        let travelled = BOOP_SPEED * loop_multiplier * time.delta_secs();
+        let hoop_count = r#loop.hoop_count;
+        let swarm = &mut r#loop.swarm;
+        swarm.angle = (swarm.angle + travelled) % (2. * PI);
+
+        swarm.pending_passes +=
+            swarm.count as f64 * hoop_count as f64 * travelled as f64 / (2. * PI as f64);
+        let passes = swarm.pending_passes.floor();
+        swarm.pending_passes -= passes;
+        let passes = passes as u64;
+
+        if passes > 0 {
+            commands.trigger(SwarmPassedHoops {
+                r#loop: loop_entity,
+                passes,
+                // Loot::add saturates, so the payout shouldn't wrap before it gets there
+                payout: if income_halted {
+                    0
+                } else {
+                    i32::try_from(passes).unwrap_or(i32::MAX)
+                },
+            });
+        }
     }
 }
 
-/// Moves boops forwards by incrementing their Orbit::current_loop_position modulating it to keep it
-/// between 0 and 2PI
-fn move_boops_forward(boops: Query<&mut Orbit, With<Boop>>, time: Res<Time>) {
-    const BOOP_SPEED: f32 = 1.2;
-    for mut orbit in boops {
-        let increase = BOOP_SPEED * time.delta_secs();
-        orbit.current_loop_position += increase;
-        orbit.current_loop_position %= 2. * PI;
-        orbit.current_loop_position;
+/// Copies BoopOrbits and BoopSwarm::angle onto the boop and swarm sprites, for Orbit to draw them.
+/// Goes loop by loop, so each sprite is written straight from its entry in the arrays
+fn mirror_boop_orbits(loop_q: Query<&Loop>, mut sprite_q: Query<(&mut Orbit, Option<&mut Boop>)>) {
+    for r#loop in &loop_q {
+        for (i, sprite) in r#loop.swarm.sprites.iter().enumerate() {
+            let Ok((mut orbit, _)) = sprite_q.get_mut(*sprite) else {
+                continue;
+            };
+            let offset = 2. * PI * i as f32 / SWARM_SPRITES as f32;
+            orbit.current_loop_position = (r#loop.swarm.angle + offset) % (2. * PI);
+        }
+
+        let orbits = &r#loop.orbits;
+        for (i, boop_entity) in r#loop.boops.iter().enumerate() {
+            let Ok((mut orbit, Some(mut boop))) = sprite_q.get_mut(*boop_entity) else {
+                continue;
+            };
+
+            orbit.current_loop_position = orbits.angles[i];
+            if boop.streak != orbits.streaks[i] {
+                boop.streak = orbits.streaks[i];
+            }
+        }
     }
 }
 
-/// Increments loot by 1 whenever a boop enters a hoop
-fn get_loot_on_boop_in_hoop(
-    mut boop_q: Query<(&Transform, &mut Boop)>,
-    loop_q: Query<&Loop>,
-    mut loot: ResMut<Loot>,
-    mut ev_writer: EventWriter<PlayBoopThroughHoop>,
+/// Takes boops that were despawned out from under their loop off its BoopOrbits, so no
+/// BoopPassedHoop is triggered for them. RemoveBoop takes its boop off before despawning it
+fn forget_despawned_boop(
+    t: Trigger<OnRemove, Boop>,
+    child_of_q: Query<&ChildOf>,
+    mut loop_q: Query<&mut Loop>,
+) {
+    let Ok(mut r#loop) = child_of_q
+        .get(t.target())
+        .and_then(|child_of| loop_q.get_mut(child_of.parent()))
+    else {
+        return;
+    };
+    let Some(i) = r#loop.boops.iter().position(|boop| *boop == t.target()) else {
+        return;
+    };
+
+    report(GameError::MissingEntity(t.target()));
+    r#loop.boops.remove(i);
+    r#loop.orbits.remove(i);
+}
 
+fn end_speed_bursts(
+    burst_q: Query<(Entity, &mut SpeedBurst)>,
+    time: Res<Time>,
     mut commands: Commands,
 ) {
-    // counterclockwise from the top left
-    // Have to convert because GIMP reports coordinates in ui-space
-    let hoop_positions: [Vec2; 8] = [
-        Vec2::new(-95., 222.),
-        Vec2::new(-225., 91.),
-        Vec2::new(-222., -93.),
-        Vec2::new(-98., -223.),
-        Vec2::new(95., -226.),
-        Vec2::new(221., -96.),
-        Vec2::new(223., 92.),
-        Vec2::new(98., 219.),
-    ];
-
-    let on_hoop_tolerance = 20.;
-
-    for r#loop in loop_q {
-        for boop in r#loop.boops.clone() {
-            let (boop_trans, mut boop) = boop_q.get_mut(boop).unwrap();
-
-            // i32 represents an index in Loop::hoop_sprites
-            let mut in_hoop: Option<i32> = None;
-            for i in 0..r#loop.hoop_count {
-                let currently_in_hoop = boop_trans
-                    .translation
-                    .truncate()
-                    .distance(hoop_positions[i as usize])
-                    <= on_hoop_tolerance;
-                if currently_in_hoop {
-                    in_hoop = Some(i);
+    for (entity, mut burst) in burst_q {
+        if burst.timer.tick(time.delta()).finished() {
+            commands.entity(entity).remove::<SpeedBurst>();
+        }
+    }
+}
+
+/// Triggers BoopPassedHoop for every hoop a boop passed through since the last tick. Each pays 1
+/// loot, more during a streak, or nothing while the loop's income is halted. Works with orbit
+/// angles instead of positions, so a boop moving far in one tick still counts every hoop it skipped
+/// over
+fn trigger_boop_passed_hoop(
+    loop_q: Query<(Entity, &mut Loop, Has<IncomeHalted>)>,
+    mut commands: Commands,
+) {
+    let slots = slots_by_angle();
+
+    for (loop_entity, mut r#loop, income_halted) in loop_q {
+        let hoop_count = r#loop.hoop_count as usize;
+        let Loop { boops, orbits, .. } = &mut *r#loop;
+
+        for (i, boop_entity) in boops.iter().enumerate() {
+            let from = orbits.previous_angles[i];
+            let travelled = orbits.travelled[i];
+            let streak = &mut orbits.streaks[i];
+
+            // Every slot is checked, empty ones break the streak. They're passed in order of angle
+            // from where the boop was, once for every lap it went in the tick
+            let first = slots.partition_point(|(angle, _)| *angle <= from);
+            for k in first.. {
+                let (angle, hoop_index) = slots[k % slots.len()];
+                let lap = (k / slots.len()) as f32 * 2. * PI;
+                if angle + lap > from + travelled {
                     break;
                 }
+
+                if hoop_index >= hoop_count {
+                    *streak = 0;
+                    continue;
+                }
+                *streak += 1;
+
+                let multiplier =
+                    (1 + (*streak / STREAK_PER_MULTIPLIER) as i32).min(MAX_STREAK_MULTIPLIER);
+                commands.trigger(BoopPassedHoop {
+                    r#loop: loop_entity,
+                    hoop_index,
+                    boop: *boop_entity,
+                    payout: if income_halted { 0 } else { multiplier },
+                });
+
+                if *streak % STREAK_PER_MULTIPLIER == 0 && !income_halted {
+                    commands.trigger(PerfectLoop {
+                        r#loop: loop_entity,
+                        boop: *boop_entity,
+                        bonus: PERFECT_LOOP_BONUS,
+                    });
+                }
             }
+        }
+    }
+}
 
-            if in_hoop.is_some() && !boop.in_hoop {
-                let in_hoop = in_hoop.unwrap();
+/// The color for a boop with this streak
+pub fn streak_color(streak: u32) -> Color {
+    let tier = (streak / STREAK_PER_MULTIPLIER) as usize;
+    STREAK_COLORS[tier.min(STREAK_COLORS.len() - 1)]
+}
 
-                **loot += 1;
-                boop.in_hoop = true;
+fn tint_boops_by_streak(boop_q: Query<(&Boop, &mut Sprite)>) {
+    for (boop, mut sprite) in boop_q {
+        let color = streak_color(boop.streak);
+        if sprite.color != color {
+            sprite.color = color;
+        }
+    }
+}
 
-                commands
-                    .entity(r#loop.hoop_sprites[in_hoop as usize].0)
-                    .insert(Animator::new(brief_fade_to_white_tween()));
+fn flash_passed_hoop(t: Trigger<BoopPassedHoop>, loop_q: Query<&Loop>, mut commands: Commands) {
+    let Some((outer_hoop, inner_hoop)) = loop_q
+        .get(t.r#loop)
+        .ok()
+        .and_then(|r#loop| r#loop.hoop_sprites.get(t.hoop_index).copied())
+    else {
+        return;
+    };
+
+    commands
+        .entity(outer_hoop)
+        .insert(Animator::new(brief_fade_to_white_tween()));
+    commands
+        .entity(inner_hoop)
+        .insert(Animator::new(brief_fade_to_white_tween()));
+}
 
-                commands
-                    .entity(r#loop.hoop_sprites[in_hoop as usize].1)
-                    .insert(Animator::new(brief_fade_to_white_tween()));
+/// Where the middle of a hoop is, relative to its loop
+pub fn hoop_position(hoop_index: usize) -> Vec2 {
+    HOOP_POSITIONS[hoop_index]
+}
 
-                ev_writer.write(PlayBoopThroughHoop);
-            }
+/// The Orbit::current_loop_position a boop is at when it's in the middle of the hoop
+fn hoop_angle(hoop_index: usize) -> f32 {
+    let position = hoop_position(hoop_index);
+    // Boops start at the top and go counterclockwise
+    f32::atan2(-position.x, position.y).rem_euclid(2. * PI)
+}
 
-            if in_hoop.is_none() {
-                boop.in_hoop = false;
-            }
-        }
-    }
+/// Every hoop slot's angle and index, smallest angle first
+fn slots_by_angle() -> [(f32, usize); MAX_HOOPS as usize] {
+    let mut slots: [(f32, usize); MAX_HOOPS as usize] = std::array::from_fn(|i| (hoop_angle(i), i));
+    slots.sort_by(|a, b| a.0.total_cmp(&b.0));
+    slots
 }
 
 pub struct LoopInfo {
     pub position: Vec2,
     pub planet: Planet,
-    pub boop_prices: [i32; 5],
-    pub hoop_prices: [i32; 8],
+    pub boop_prices: [Price; 5],
+    pub hoop_prices: [Price; 8],
 }
 
 /// Spawns a loop, returning the loop, buy boop btn, and buy hoop btn, in that order
@@ -257,12 +637,17 @@ pub fn spawn_loop(
             },
             Loop {
                 boops: Vec::default(),
+                orbits: BoopOrbits::default(),
+                swarm: BoopSwarm::default(),
                 hoop_count: 0,
                 hoop_sprites: Vec::default(),
                 planet: loop_info.planet,
             },
             ZIndex(-2),
+            Focusable::new(LOOP_RADIUS),
+            PlayerInput::ClickPlanet(loop_info.planet),
         ))
+        .observe(press_on_click)
         .id();
 
     commands.queue(AddBoop(r#loop));
@@ -277,21 +662,34 @@ pub fn spawn_loop(
         asset_server,
     );
 
+    commands
+        .entity(boop)
+        .insert(PlayerInput::BuyBoop(loop_info.planet));
+    commands
+        .entity(hoop)
+        .insert(PlayerInput::BuyHoop(loop_info.planet));
+
     (r#loop, boop, hoop)
 }
 
 /// Custom EntityCommand that adds a hoop to a loop
-/// panics if you try to add a hoop to a loop that already has MAX_HOOPS or if the entity does not
+/// errors if you try to add a hoop to a loop that already has MAX_HOOPS or if the entity does not
 /// contain the Loop Component
-pub struct AddHoop(Entity);
-impl Command for AddHoop {
-    fn apply(self, world: &mut World) {
-        let r#loop = world.entity_mut(self.0);
-
-        let r#loop_component = r#loop.get::<Loop>().unwrap();
+pub struct AddHoop(pub Entity);
+impl Command<GameResult> for AddHoop {
+    fn apply(self, world: &mut World) -> GameResult {
+        let r#loop = world
+            .get_entity(self.0)
+            .map_err(|_| GameError::MissingEntity(self.0))?;
+
+        let r#loop_component = r#loop.get::<Loop>().ok_or(GameError::NotALoop(self.0))?;
         let hoop_count = r#loop_component.hoop_count;
         let planet = r#loop_component.planet;
 
+        if hoop_count >= MAX_HOOPS {
+            return Err(GameError::MaxHoops(self.0));
+        }
+
         let asset_server = world.get_resource_mut::<AssetServer>().unwrap();
 
         let outer_hoop_image = asset_server.load(planet.get_outer_hoop_path(hoop_count + 1));
@@ -328,10 +726,9 @@ impl Command for AddHoop {
         r#loop.add_child(outer_hoop);
         r#loop.add_child(inner_hoop);
 
-        let mut r#loop = r#loop.get_mut::<Loop>().unwrap();
-        if r#loop.hoop_count >= MAX_HOOPS {
-            panic!("Added a hoop to a loop that already has max hoops");
-        }
+        let mut r#loop = r#loop
+            .get_mut::<Loop>()
+            .ok_or(GameError::NotALoop(self.0))?;
         r#loop.hoop_count += 1;
 
         r#loop.hoop_sprites.push((outer_hoop, inner_hoop));
@@ -340,6 +737,8 @@ impl Command for AddHoop {
         if is_max {
             world.trigger(AllHoopsBought);
         }
+
+        Ok(())
     }
 }
 
@@ -350,22 +749,31 @@ impl From<Entity> for AddHoop {
     }
 }
 
-/// Custom EntityCommand that adds a boop to a loop
-/// panics if you try to add a boop to a loop that already has MAX_BOOPS
-/// Does not check if entity is a loop, behavior is undefined if so
+/// Custom EntityCommand that adds a boop to a loop, past MAX_BOOP_SPRITES it joins the loop's swarm
+/// errors if the entity does not contain the Loop Component
 pub struct AddBoop(pub Entity);
-impl Command for AddBoop {
-    fn apply(self, world: &mut World) {
+impl Command<GameResult> for AddBoop {
+    fn apply(self, world: &mut World) -> GameResult {
         let r#loop = self.0;
+
+        let loop_component = world
+            .get_entity(r#loop)
+            .map_err(|_| GameError::MissingEntity(r#loop))?
+            .get::<Loop>()
+            .ok_or(GameError::NotALoop(r#loop))?;
+        let boop_count = loop_component.boops.len();
+        let planet = loop_component.planet;
+
+        if boop_count >= MAX_BOOP_SPRITES {
+            return add_to_swarm(world, r#loop);
+        }
+
         let asset_server = world.get_resource_mut::<AssetServer>().unwrap();
         let boop_image = asset_server.load("boop.png");
 
-        const BOOP_TO_LOOP_MARGIN: f32 = 15.;
-        const SCALE: f32 = 0.1;
-
         let starting_transform = Transform {
             translation: Vec3::new(0., LOOP_RADIUS + BOOP_TO_LOOP_MARGIN, 1.),
-            scale: Vec3::splat(SCALE),
+            scale: Vec3::splat(BOOP_SCALE),
             ..default()
         };
 
@@ -373,25 +781,86 @@ impl Command for AddBoop {
             .spawn((
                 Sprite::from_image(boop_image),
                 starting_transform,
-                Boop { ..default() },
+                Boop { streak: 0 },
                 Orbit {
                     current_loop_position: 0.,
                     starting_transform,
                 },
+                PlayerInput::ClickBoop(planet, boop_count),
             ))
+            .observe(press_on_click)
             .id();
 
         let mut r#loop = world.entity_mut(r#loop);
         r#loop.add_child(new_boop);
 
-        let mut r#loop = r#loop.get_mut::<Loop>().unwrap();
-        let boop_count = r#loop.boops.len();
+        let mut r#loop = r#loop
+            .get_mut::<Loop>()
+            .ok_or(GameError::NotALoop(self.0))?;
+        r#loop.boops.push(new_boop);
+        r#loop.orbits.push();
 
-        if boop_count >= MAX_BOOPS {
-            panic!("Added a boop to a loop that already has max boops");
+        Ok(())
+    }
+}
+
+const BOOP_SCALE: f32 = 0.1;
+
+/// Swarm sprites grow with how many boops they stand for, by this much for every 10x
+const SWARM_GROWTH_PER_MAGNITUDE: f32 = 0.5;
+const SWARM_COLOR: Color = Color::srgba(1., 1., 1., 0.6);
+
+fn add_to_swarm(world: &mut World, r#loop: Entity) -> GameResult {
+    let mut loop_component = world
+        .get_mut::<Loop>(r#loop)
+        .ok_or(GameError::NotALoop(r#loop))?;
+    loop_component.swarm.count += 1;
+    let count = loop_component.swarm.count;
+    let mut sprites = loop_component.swarm.sprites.clone();
+
+    if sprites.is_empty() {
+        let boop_image = world.resource::<AssetServer>().load("boop.png");
+        for _ in 0..SWARM_SPRITES {
+            let sprite = world
+                .spawn((
+                    Sprite {
+                        image: boop_image.clone(),
+                        color: SWARM_COLOR,
+                        ..default()
+                    },
+                    SwarmSprite,
+                    Orbit {
+                        current_loop_position: 0.,
+                        starting_transform: Transform::default(),
+                    },
+                    Pickable::IGNORE,
+                ))
+                .id();
+            world.entity_mut(r#loop).add_child(sprite);
+            sprites.push(sprite);
         }
+        world
+            .get_mut::<Loop>(r#loop)
+            .ok_or(GameError::NotALoop(r#loop))?
+            .swarm
+            .sprites = sprites.clone();
+    }
 
-        r#loop.boops.push(new_boop);
+    scale_swarm_sprites(world, &sprites, count);
+
+    Ok(())
+}
+
+fn scale_swarm_sprites(world: &mut World, sprites: &[Entity], count: u64) {
+    let scale = BOOP_SCALE * (1. + SWARM_GROWTH_PER_MAGNITUDE * (count as f32).log10());
+    for sprite in sprites {
+        if let Some(mut orbit) = world.get_mut::<Orbit>(*sprite) {
+            orbit.starting_transform = Transform {
+                translation: Vec3::new(0., LOOP_RADIUS + BOOP_TO_LOOP_MARGIN, 0.9),
+                scale: Vec3::splat(scale),
+                ..default()
+            };
+        }
     }
 }
 
@@ -402,6 +871,49 @@ impl From<Entity> for AddBoop {
     }
 }
 
+/// Custom EntityCommand that takes a boop off a loop, from its swarm if it has one and otherwise
+/// the newest boop with a sprite
+/// errors if the loop is down to its last boop or if the entity does not contain the Loop
+/// Component
+pub struct RemoveBoop(pub Entity);
+impl Command<GameResult> for RemoveBoop {
+    fn apply(self, world: &mut World) -> GameResult {
+        let r#loop = self.0;
+
+        let mut loop_component = world
+            .get_mut::<Loop>(r#loop)
+            .ok_or(GameError::NotALoop(r#loop))?;
+        if loop_component.boop_count() <= 1 {
+            return Err(GameError::LastBoop(r#loop));
+        }
+
+        if loop_component.swarm.count > 0 {
+            loop_component.swarm.count -= 1;
+            let count = loop_component.swarm.count;
+            if count > 0 {
+                let sprites = loop_component.swarm.sprites.clone();
+                scale_swarm_sprites(world, &sprites, count);
+                return Ok(());
+            }
+
+            // Not a swarm anymore
+            loop_component.swarm.pending_passes = 0.;
+            for sprite in std::mem::take(&mut loop_component.swarm.sprites) {
+                world.despawn(sprite);
+            }
+            return Ok(());
+        }
+
+        let boop = loop_component.boops.pop();
+        loop_component.orbits.pop();
+        if let Some(boop) = boop {
+            world.despawn(boop);
+        }
+
+        Ok(())
+    }
+}
+
 fn brief_fade_to_white_tween() -> Tween<Sprite> {
     Tween::new(
         EaseFunction::QuadraticInOut,
@@ -0,0 +1,90 @@
+use bevy::prelude::*;
+use bevy_tweening::TweeningPlugin;
+
+pub mod affordability;
+pub mod audio_sink_volume_lens;
+pub mod background;
+pub mod band_pass;
+pub mod boop_effects;
+pub mod buy_boops_and_hoops;
+pub mod click_bonus;
+#[cfg(feature = "dev")]
+pub mod dev_console;
+pub mod focus;
+pub mod galaxy_events;
+pub mod game_error;
+pub mod game_rng;
+pub mod game_speed;
+pub mod gameplay_events;
+pub mod headless;
+pub mod hoops_boops_loops;
+pub mod locked_planets;
+pub mod loot;
+pub mod orbit_starting_transform_y_lens;
+pub mod play_hoop_through_boop_sounds;
+pub mod prices;
+pub mod projection_scale_lens;
+pub mod quests;
+pub mod replay;
+pub mod scales;
+pub mod screen_size;
+pub mod soundtrack;
+pub mod spatial_audio;
+pub mod titlescreen;
+pub mod tooltip;
+pub mod transfer_lanes;
+pub mod transition_to_all_planets;
+pub mod transition_to_first_planet;
+pub mod tutorial;
+pub mod wallet;
+
+use audio_sink_volume_lens::audio_sink_volume_lens_plugin;
+use background::background_plugin;
+use buy_boops_and_hoops::buy_boops_and_hoops_plugin;
+use hoops_boops_loops::hoops_boops_loops_plugin;
+use loot::loot_plugin;
+use orbit_starting_transform_y_lens::orbit_starting_transform_y_lens_plugin;
+use projection_scale_lens::projection_scale_lens_plugin;
+use soundtrack::soundtrack_plugin;
+use titlescreen::titlescreen_plugin;
+
+/// All of the game, without the window, camera or anything that needs a renderer. See
+/// headless::headless_app for running it in tests
+pub fn plugin(app: &mut App) {
+    app.add_plugins((
+        (
+            game_rng::plugin,
+            replay::plugin,
+            game_speed::plugin,
+            galaxy_events::plugin,
+            click_bonus::plugin,
+            boop_effects::plugin,
+            transfer_lanes::plugin,
+            wallet::plugin,
+            quests::plugin,
+            tutorial::plugin,
+            tooltip::plugin,
+            affordability::plugin,
+        ),
+        hoops_boops_loops_plugin,
+        background_plugin,
+        loot_plugin,
+        buy_boops_and_hoops_plugin,
+        TweeningPlugin,
+        soundtrack_plugin,
+        titlescreen_plugin,
+        (
+            projection_scale_lens_plugin,
+            orbit_starting_transform_y_lens_plugin,
+            audio_sink_volume_lens_plugin,
+        ),
+        transition_to_all_planets::plugin,
+        locked_planets::plugin,
+        play_hoop_through_boop_sounds::plugin,
+        focus::plugin,
+        game_error::plugin,
+    ));
+
+    #[cfg(feature = "dev")]
+    app.add_plugins(dev_console::plugin);
+}
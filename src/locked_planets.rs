@@ -1,12 +1,18 @@
 mod tweens;
 
-use crate::hoops_boops_loops::{LoopInfo, Planet, spawn_loop};
+use crate::affordability::PriceTag;
+use crate::focus::{Focusable, Focused, Press, press_on_click};
+use crate::game_error::GameError;
+use crate::gameplay_events::{PlanetUnlocked, PurchaseKind, Purchased};
+use crate::hoops_boops_loops::{Loop, LoopInfo, Planet, loot_per_second, spawn_loop};
 use crate::locked_planets::tweens::*;
-use crate::loot::Loot;
 use crate::prices::*;
-use crate::scales::ZOOMED_OUT_PLANET_SCALE;
+use crate::replay::PlayerInput;
+use crate::scales::{PLANET_FILE_RADIUS, ZOOMED_OUT_PLANET_SCALE};
 use crate::screen_size::SCREEN_SIZE;
-use bevy::audio::PlaybackMode;
+use crate::spatial_audio::SPATIAL_DESPAWN;
+use crate::tooltip::{Hovered, Tooltip, hide_tooltip, show_tooltip};
+use crate::wallet::{Currency, Funds, Price};
 use bevy::prelude::*;
 use bevy_tweening::Animator;
 
@@ -15,6 +21,12 @@ pub struct LockedPlanet {
     planet: Planet,
 }
 
+impl LockedPlanet {
+    pub fn planet(&self) -> Planet {
+        self.planet
+    }
+}
+
 #[derive(Event)]
 pub struct BoughtLoop;
 
@@ -28,7 +40,8 @@ struct Handles {
 }
 
 pub fn plugin(app: &mut App) {
-    app.add_systems(Startup, load_handles);
+    app.add_systems(Startup, load_handles)
+        .add_systems(Update, update_locked_planet_tooltips);
 }
 
 /// Command wrapper around spawn_locked_planet
@@ -68,8 +81,17 @@ impl Command for SpawnLockedPlanet {
                     planet: self.planet,
                 },
                 Pickable::default(),
+                Focusable::new(PLANET_FILE_RADIUS),
+                PlayerInput::UnlockPlanet(self.planet),
+                Tooltip::default(),
+                PriceTag {
+                    price: Some(self.planet.get_price()),
+                    shape: planet_img.clone(),
+                    ring_radius: None,
+                },
             ))
-            .observe(buy_loop_on_click)
+            .observe(press_on_click)
+            .observe(buy_loop_on_press)
             .observe(highlight_on_hover)
             .observe(unhighlight_on_out)
             .observe(move |_: Trigger<BoughtLoop>, mut commands: Commands| {
@@ -83,39 +105,37 @@ impl Command for SpawnLockedPlanet {
     }
 }
 
-fn buy_loop_on_click(
-    t: Trigger<Pointer<Click>>,
+fn buy_loop_on_press(
+    t: Trigger<Press>,
     transform_q: Query<&Transform, With<LockedPlanet>>,
     locked_planet_q: Query<&LockedPlanet>,
-    mut loot: ResMut<Loot>,
+    mut funds: Funds,
+    mut focused: ResMut<Focused>,
 
     mut commands: Commands,
     asset_server: Res<AssetServer>,
-) {
-    let planet = locked_planet_q.get(t.target).unwrap().planet;
+) -> Result {
+    let planet = locked_planet_q
+        .get(t.target())
+        .map_err(|_| GameError::MissingEntity(t.target()))?
+        .planet;
     let price = planet.get_price();
 
-    if **loot > price {
-        commands.spawn((
-            AudioPlayer::new(asset_server.load("successful-buy.ogg")),
-            PlaybackSettings {
-                mode: PlaybackMode::Despawn,
-                ..default()
-            },
-        ));
+    if funds.spend(&price) {
         commands
-            .entity(t.target)
+            .entity(t.target())
             .insert(Animator::new(fade_in_blue()));
 
-        **loot -= price;
-        let position = transform_q.get(t.target).unwrap();
+        let position = transform_q
+            .get(t.target())
+            .map_err(|_| GameError::MissingEntity(t.target()))?;
 
         let (r#loop, _, _) = spawn_loop(
             LoopInfo {
                 position: position.translation.truncate(),
                 planet: planet,
                 boop_prices: FIRST_PLANET_BOOP_PRICES,
-                hoop_prices: FIRST_PLANET_HOOP_PRICES,
+                hoop_prices: GALAXY_HOOP_PRICES,
             },
             &mut commands,
             &asset_server,
@@ -126,33 +146,91 @@ fn buy_loop_on_click(
             .entry::<Transform>()
             .and_modify(|mut t| t.scale = Vec3::splat(ZOOMED_OUT_PLANET_SCALE));
 
+        commands.trigger(Purchased {
+            kind: PurchaseKind::Loop,
+            r#loop,
+            price,
+        });
+        commands.trigger(PlanetUnlocked { planet });
+
+        // Keep focus on the same spot for keyboard and gamepad players
+        if **focused == Some(t.target()) {
+            **focused = Some(r#loop);
+        }
+
         // Despawns the prices display
-        commands.entity(t.target).trigger(BoughtLoop).despawn();
+        commands.entity(t.target()).trigger(BoughtLoop).despawn();
     } else {
+        let position = transform_q
+            .get(t.target())
+            .map(|transform| transform.translation)
+            .unwrap_or_default();
         commands.spawn((
             AudioPlayer::new(asset_server.load("unsuccessful-buy.ogg")),
-            PlaybackSettings {
-                mode: PlaybackMode::Despawn,
-                ..default()
-            },
+            Transform::from_translation(position),
+            SPATIAL_DESPAWN,
         ));
 
         commands
-            .entity(t.target)
+            .entity(t.target())
             .insert(Animator::new(fade_in_orange()));
     }
+
+    Ok(())
 }
 
-fn highlight_on_hover(t: Trigger<Pointer<Over>>, mut commands: Commands, handles: Res<Handles>) {
+fn highlight_on_hover(
+    t: Trigger<Pointer<Over>>,
+    mut commands: Commands,
+    handles: Res<Handles>,
+    mut hovered: ResMut<Hovered>,
+) {
     commands
         .entity(t.target)
         .insert(Sprite::from_image(handles.onhover.clone()));
+    show_tooltip(t.target, &mut hovered);
 }
 
-fn unhighlight_on_out(t: Trigger<Pointer<Out>>, mut commands: Commands, handles: Res<Handles>) {
+fn unhighlight_on_out(
+    t: Trigger<Pointer<Out>>,
+    mut commands: Commands,
+    handles: Res<Handles>,
+    mut hovered: ResMut<Hovered>,
+) {
     commands
         .entity(t.target)
         .insert(Sprite::from_image(handles.prehover.clone()));
+    hide_tooltip(t.target, &mut hovered);
+}
+
+/// Explains what unlocking the hovered planet gets, with how many planets are unlocked, the loot
+/// per second the new planet's first boop and hoop earn and the full price. Only the hovered
+/// planet's tooltip is shown, so only it is kept up to date
+fn update_locked_planet_tooltips(
+    hovered: Res<Hovered>,
+    mut locked_planet_q: Query<(&LockedPlanet, &mut Tooltip)>,
+    loop_q: Query<(), With<Loop>>,
+) {
+    let unlocked = loop_q.iter().count();
+    let total = unlocked + locked_planet_q.iter().count();
+    let Some((locked_planet, mut tooltip)) =
+        hovered.and_then(|entity| locked_planet_q.get_mut(entity).ok())
+    else {
+        return;
+    };
+
+    let planet = locked_planet.planet;
+    let produces = match Currency::produced_on(planet) {
+        Some(currency) => format!(". Makes {} too", currency.name()),
+        None => String::new(),
+    };
+
+    tooltip.set_if_neq(Tooltip(format!(
+        "Planets {unlocked}/{total}\nA new planet with a boop and a hoop{produces}\n+{:.1} \
+         loot/s\nCosts {}",
+        loot_per_second(1, 1),
+        planet.get_price()
+    )));
 }
 
 fn spawn_price_display(
@@ -160,13 +238,14 @@ fn spawn_price_display(
     loot_symbol: Handle<Image>,
     spacey_font: Handle<Font>,
     pos: Vec2,
-    price: i32,
+    price: Price,
 ) -> Entity {
     // For animation
     const STARTING_LEFT_OFFSET: f32 = -1400.;
     const TOP_OFFSET: f32 = 135.;
     let top = (SCREEN_SIZE.y / 2.) - pos.y + TOP_OFFSET;
-    let width = 150.;
+    // Each currency besides loot gets its own pill, widening the display
+    let width = 150. + 70. * price.extras().count() as f32;
 
     let ending_left: f32 = (SCREEN_SIZE.x / 2.) + pos.x - width / 2.;
 
@@ -212,16 +291,16 @@ fn spawn_price_display(
         })
         .id();
 
-    let price_string = if price >= 100 {
-        (price / 100).to_string() + "h"
+    let price_string = if price.loot >= 100 {
+        (price.loot / 100).to_string() + "h"
     } else {
-        price.to_string()
+        price.loot.to_string()
     };
 
     commands.entity(text_container).with_child((
         Text::new(price_string),
         TextFont {
-            font: spacey_font,
+            font: spacey_font.clone(),
             font_size: 40.,
             ..default()
         },
@@ -231,9 +310,45 @@ fn spawn_price_display(
         },
     ));
 
+    let extras: Vec<Entity> = price
+        .extras()
+        .map(|(currency, amount)| {
+            commands
+                .spawn(Node {
+                    width: Val::Px(70.),
+                    height: Val::Percent(100.),
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    column_gap: Val::Px(5.),
+                    ..default()
+                })
+                .with_children(|pill| {
+                    pill.spawn((
+                        Node {
+                            width: Val::Px(14.),
+                            height: Val::Px(14.),
+                            ..default()
+                        },
+                        BackgroundColor(currency.color()),
+                        BorderRadius::MAX,
+                    ));
+                    pill.spawn((
+                        Text::new(amount.to_string()),
+                        TextFont {
+                            font: spacey_font.clone(),
+                            font_size: 30.,
+                            ..default()
+                        },
+                    ));
+                })
+                .id()
+        })
+        .collect();
+
     commands
         .entity(price_display)
         .add_children(&[loot_symbol, text_container])
+        .add_children(&extras)
         .insert(Animator::new(wait_seconds(10., starting_left, top).then(
             slide_in_from_left_tween(starting_left, ending_left, top),
         )));
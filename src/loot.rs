@@ -1,5 +1,9 @@
 mod tweens;
 
+use crate::gameplay_events::{
+    BoopPassedHoop, BoopTransferred, PerfectLoop, QuestCompleted, SwarmPassedHoops,
+};
+use crate::wallet::{CURRENCIES, Currency, Wallet};
 use bevy::prelude::*;
 use bevy_tweening::Animator;
 use tweens::*;
@@ -8,14 +12,26 @@ use tweens::*;
 #[derive(Resource, Deref, DerefMut)]
 pub struct Loot(i32);
 
+impl Loot {
+    /// Saturates at i32::MAX, boops are unlimited so loot can grow big enough to overflow
+    pub fn add(&mut self, amount: i32) {
+        self.0 = self.0.saturating_add(amount);
+    }
+}
+
 /// Marker struct for the Loot Display
 #[derive(Component)]
-struct LootDisplay;
+pub struct LootDisplay;
 
 /// Marker struct for the entity that contains the Text of the LootDisplay representing current loot
 #[derive(Component)]
 struct CurrentLootText;
 
+/// The amount of a currency in the Wallet, next to the loot on the LootDisplay. Its parent stays
+/// hidden until the player has some
+#[derive(Component)]
+struct CurrencyText(Currency);
+
 /// The starting position left is off screen because it will slide in. See tweens::slide_in_from_right_tween
 const STARTING_LEFT_POSITION: Val = Val::Px(200.);
 
@@ -23,11 +39,39 @@ pub fn loot_plugin(app: &mut App) {
     app.add_systems(Startup, spawn_display)
         .add_systems(
             FixedUpdate,
-            update_loot_display.run_if(resource_changed::<Loot>),
+            (
+                update_loot_display.run_if(resource_changed::<Loot>),
+                update_currency_display.run_if(resource_changed::<Wallet>),
+            ),
         )
+        .add_observer(add_payout)
+        .add_observer(add_perfect_loop_bonus)
+        .add_observer(add_swarm_payout)
+        .add_observer(add_transfer_bonus)
+        .add_observer(add_quest_reward)
         .insert_resource(Loot(0));
 }
 
+fn add_payout(t: Trigger<BoopPassedHoop>, mut loot: ResMut<Loot>) {
+    loot.add(t.payout);
+}
+
+fn add_swarm_payout(t: Trigger<SwarmPassedHoops>, mut loot: ResMut<Loot>) {
+    loot.add(t.payout);
+}
+
+fn add_perfect_loop_bonus(t: Trigger<PerfectLoop>, mut loot: ResMut<Loot>) {
+    loot.add(t.bonus);
+}
+
+fn add_transfer_bonus(t: Trigger<BoopTransferred>, mut loot: ResMut<Loot>) {
+    loot.add(t.bonus);
+}
+
+fn add_quest_reward(t: Trigger<QuestCompleted>, mut loot: ResMut<Loot>) {
+    loot.add(t.reward);
+}
+
 /// Wrap the system in a custom command for easier calling
 pub struct ShowDisplay;
 impl Command for ShowDisplay {
@@ -93,7 +137,7 @@ fn spawn_display(mut commands: Commands, asset_server: Res<AssetServer>) {
         CurrentLootText,
         Text::default(),
         TextFont {
-            font: spacey_font,
+            font: spacey_font.clone(),
             font_size: 40.,
             ..default()
         },
@@ -106,9 +150,62 @@ fn spawn_display(mut commands: Commands, asset_server: Res<AssetServer>) {
     commands
         .entity(loot_display)
         .add_children(&[loot_symbol, loot_text_container]);
+
+    for currency in CURRENCIES {
+        let currency_container = commands
+            .spawn(Node {
+                display: Display::None,
+                height: Val::Percent(100.),
+                align_items: AlignItems::Center,
+                margin: UiRect::right(Val::Px(15.)),
+                column_gap: Val::Px(8.),
+                ..default()
+            })
+            .with_children(|container| {
+                container.spawn((
+                    Node {
+                        width: Val::Px(18.),
+                        height: Val::Px(18.),
+                        ..default()
+                    },
+                    BackgroundColor(currency.color()),
+                    BorderRadius::MAX,
+                ));
+                container.spawn((
+                    CurrencyText(currency),
+                    Text::default(),
+                    TextFont {
+                        font: spacey_font.clone(),
+                        font_size: 30.,
+                        ..default()
+                    },
+                ));
+            })
+            .id();
+        commands.entity(loot_display).add_child(currency_container);
+    }
 }
 
 /// Update the loot display
 fn update_loot_display(loot: Res<Loot>, mut text: Single<&mut Text, With<CurrentLootText>>) {
     text.0 = loot.to_string();
 }
+
+/// Update every currency's amount, showing the ones the player has some of
+fn update_currency_display(
+    wallet: Res<Wallet>,
+    text_q: Query<(&CurrencyText, &mut Text, &ChildOf)>,
+    mut node_q: Query<&mut Node>,
+) {
+    for (currency_text, mut text, child_of) in text_q {
+        let amount = wallet.get(currency_text.0);
+        text.0 = amount.to_string();
+        if let Ok(mut node) = node_q.get_mut(child_of.parent()) {
+            node.display = if amount > 0 {
+                Display::Flex
+            } else {
+                Display::None
+            };
+        }
+    }
+}
@@ -1,61 +1,75 @@
+use bevy::ecs::error::GLOBAL_ERROR_HANDLER;
+use bevy::picking::PickingPlugin;
 use bevy::prelude::*;
+use bevy::time::TimeUpdateStrategy;
 use bevy::window::WindowResolution;
-use bevy_tweening::TweeningPlugin;
+use hoops_boops_and_loops::focus::focus_ring_plugin;
+use hoops_boops_and_loops::game_error::{self, report};
+use hoops_boops_and_loops::replay::{Replay, TICK, start_playback};
+use hoops_boops_and_loops::screen_size::SCREEN_SIZE;
+use hoops_boops_and_loops::spatial_audio;
+use hoops_boops_and_loops::tutorial::{
+    SaveTutorialDone, TUTORIAL_SAVE_PATH, Tutorial, load_tutorial_done,
+};
 
-mod background;
-mod buy_boops_and_hoops;
-mod hoops_boops_loops;
-mod locked_planets;
-mod loot;
-mod orbit_starting_transform_y_lens;
-mod play_hoop_through_boop_sounds;
-mod prices;
-mod projection_scale_lens;
-mod scales;
-mod screen_size;
-mod soundtrack;
-mod titlescreen;
-mod transition_to_all_planets;
-mod transition_to_first_planet;
+fn main() {
+    GLOBAL_ERROR_HANDLER
+        .set(game_error::handle_error)
+        .expect("The error handler can only be set once");
 
-use crate::orbit_starting_transform_y_lens::orbit_starting_transform_y_lens_plugin;
-use background::background_plugin;
-use buy_boops_and_hoops::buy_boops_and_hoops_plugin;
-use hoops_boops_loops::hoops_boops_loops_plugin;
-use loot::loot_plugin;
-use projection_scale_lens::projection_scale_lens_plugin;
-use screen_size::SCREEN_SIZE;
-use soundtrack::soundtrack_plugin;
-use titlescreen::titlescreen_plugin;
+    // `--replay <file>` plays back a recorded session, see replay.rs
+    let args: Vec<String> = std::env::args().collect();
+    let replay_path = args
+        .iter()
+        .position(|arg| arg == "--replay")
+        .and_then(|i| args.get(i + 1));
 
-fn main() {
-    App::new()
-        .add_plugins((
-            DefaultPlugins.set(WindowPlugin {
-                primary_window: Some(Window {
-                    resolution: WindowResolution::new(SCREEN_SIZE.x, SCREEN_SIZE.y),
-                    title: "Hoops & Boops and don't forget about loops!".to_string(),
-                    ..default()
-                }),
+    let mut app = App::new();
+    app.add_plugins((
+        DefaultPlugins.set(WindowPlugin {
+            primary_window: Some(Window {
+                resolution: WindowResolution::new(SCREEN_SIZE.x, SCREEN_SIZE.y),
+                title: "Hoops & Boops and don't forget about loops!".to_string(),
                 ..default()
             }),
-            hoops_boops_loops_plugin,
-            background_plugin,
-            loot_plugin,
-            buy_boops_and_hoops_plugin,
-            TweeningPlugin,
-            soundtrack_plugin,
-            titlescreen_plugin,
-            projection_scale_lens_plugin,
-            orbit_starting_transform_y_lens_plugin,
-            transition_to_all_planets::plugin,
-            locked_planets::plugin,
-            play_hoop_through_boop_sounds::plugin,
-        ))
-        .add_systems(Startup, setup_camera)
-        .run();
+            ..default()
+        }),
+        hoops_boops_and_loops::plugin,
+        focus_ring_plugin,
+    ))
+    .add_systems(Startup, setup_camera);
+
+    if let Some(path) = replay_path {
+        let replay = match Replay::load(path) {
+            Ok(replay) => replay,
+            Err(error) => {
+                eprintln!("{error}");
+                std::process::exit(1);
+            }
+        };
+
+        start_playback(&mut app, replay);
+        // One fixed tick per frame no matter the frame rate, and no clicking during playback
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(TICK))
+            .insert_resource(PickingPlugin {
+                is_input_enabled: false,
+                ..default()
+            });
+    } else {
+        // A save that can't be read doesn't make this the first launch
+        match load_tutorial_done(TUTORIAL_SAVE_PATH) {
+            Ok(false) => {
+                app.insert_resource(Tutorial::Step(0))
+                    .insert_resource(SaveTutorialDone);
+            }
+            Ok(true) => {}
+            Err(error) => report(error),
+        }
+    }
+
+    app.run();
 }
 
 fn setup_camera(mut commands: Commands) {
-    commands.spawn(Camera2d);
+    commands.spawn((Camera2d, spatial_audio::listener()));
 }
@@ -1,42 +1,101 @@
-use bevy::audio::PlaybackMode;
+/// Every hoop pass plays a note. Each planet plays a different mode of the same major scale, going
+/// up a step with each hoop, so every planet sounds good together. Notes wait for the next step
+/// of the soundtrack's beat grid, so a busy galaxy plays a melody instead of a wall of noise
+use crate::gameplay_events::BoopPassedHoop;
+use crate::hoops_boops_loops::{Loop, Planet, hoop_position};
+use crate::soundtrack::BeatClock;
+use crate::spatial_audio::SPATIAL_DESPAWN;
 use bevy::prelude::*;
-use std::time::Duration;
 
-#[derive(Event)]
-pub struct PlayBoopThroughHoop;
+/// Semitones above the root of each degree of a major scale
+const MAJOR_SCALE: [i32; 7] = [0, 2, 4, 5, 7, 9, 11];
+
+/// Shifts every note down so the first planet's first hoop is a bit lower than the recording
+const TRANSPOSE: i32 = -9;
+
+/// How many notes can sound at once
+const MAX_VOICES: usize = 6;
+
+/// Notes waiting for the next step of the beat grid
+#[derive(Resource, Default)]
+struct QueuedNotes(Vec<QueuedNote>);
+
+struct QueuedNote {
+    /// The playback speed, see note_speed
+    speed: f32,
+    /// Where the hoop is, for panning
+    position: Vec3,
+}
+
+/// Marker struct for a playing note
+#[derive(Component)]
+struct Note;
 
 pub fn plugin(app: &mut App) {
-    app.add_event::<PlayBoopThroughHoop>()
-        .add_systems(FixedUpdate, play_hoop_through_boop_sounds);
+    app.init_resource::<QueuedNotes>()
+        .add_observer(queue_note)
+        .add_systems(Update, play_queued_notes_on_step);
 }
 
-fn play_hoop_through_boop_sounds(
-    mut sound_evs: EventReader<PlayBoopThroughHoop>,
-    mut commands: Commands,
-    asset_server: Res<AssetServer>,
+/// The playback speed that pitches the recording to the note for this planet and hoop
+pub fn note_speed(planet: Planet, hoop_index: usize) -> f32 {
+    // The first planet starts on the first degree, the second planet on the second and so on
+    let degree = planet as usize + hoop_index;
+    let semitones =
+        12 * (degree / MAJOR_SCALE.len()) as i32 + MAJOR_SCALE[degree % MAJOR_SCALE.len()];
 
-    mut time_since_last_boop: Local<Duration>,
-    mut is_ready_to_boop: Local<bool>,
-    time: Res<Time<Real>>,
+    2_f32.powf((semitones + TRANSPOSE) as f32 / 12.)
+}
+
+fn queue_note(
+    t: Trigger<BoopPassedHoop>,
+    loop_q: Query<(&Loop, &GlobalTransform)>,
+    mut queued: ResMut<QueuedNotes>,
 ) {
-    const BOOP_INTERVAL: Duration = Duration::from_millis(150);
+    let Ok((r#loop, loop_transform)) = loop_q.get(t.r#loop) else {
+        return;
+    };
 
-    *time_since_last_boop += time.delta();
+    // The same note twice on one step just sounds louder
+    let speed = note_speed(r#loop.planet(), t.hoop_index);
+    if queued.0.iter().any(|note| note.speed == speed) {
+        return;
+    }
 
-    *is_ready_to_boop = *is_ready_to_boop || sound_evs.read().count() > 0;
+    queued.0.push(QueuedNote {
+        speed,
+        position: loop_transform.transform_point(hoop_position(t.hoop_index).extend(0.)),
+    });
+}
+
+/// On the first frame of every step, plays the notes queued since the last one. Notes that don't
+/// get a voice are dropped instead of piling up for later steps
+fn play_queued_notes_on_step(
+    mut queued: ResMut<QueuedNotes>,
+    note_q: Query<(), With<Note>>,
+    beat_clock: Res<BeatClock>,
+    time: Res<Time<Real>>,
+    mut last_step: Local<u64>,
 
-    if *time_since_last_boop > BOOP_INTERVAL && *is_ready_to_boop {
-        let sound = asset_server.load("boop-going-through-hoop.ogg");
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+) {
+    let step = beat_clock.step(time.elapsed());
+    if step == *last_step {
+        return;
+    }
+    *last_step = step;
 
+    let free_voices = MAX_VOICES.saturating_sub(note_q.iter().count());
+    for note in queued.0.drain(..).take(free_voices) {
         commands.spawn((
-            AudioPlayer::new(sound.clone()),
+            Note,
+            AudioPlayer::new(asset_server.load("boop-going-through-hoop.ogg")),
+            Transform::from_translation(note.position),
             PlaybackSettings {
-                mode: PlaybackMode::Despawn,
-                ..default()
+                speed: note.speed,
+                ..SPATIAL_DESPAWN
             },
         ));
-
-        *time_since_last_boop = Duration::ZERO;
-        *is_ready_to_boop = false;
     }
 }
@@ -1,3 +1,51 @@
-pub const FIRST_PLANET_BOOP_PRICES: [i32; 5] = [1, 5, 10, 20, 30];
-pub const FIRST_PLANET_HOOP_PRICES: [i32; 8] = [1, 5, 10, 20, 30, 50, 100, 200];
-pub const PLANET_PRICES: [i32; 6] = [0, 20, 40, 80, 200, 300];
+use crate::wallet::Currency::{Ice, Stardust};
+use crate::wallet::Price;
+
+pub const FIRST_PLANET_BOOP_PRICES: [Price; 5] = [
+    Price::loot(1),
+    Price::loot(5),
+    Price::loot(10),
+    Price::loot(20),
+    Price::loot(30),
+];
+/// Boops can be bought forever, each past the price list costs this much more than the last
+pub const BOOP_PRICE_GROWTH: f32 = 1.15;
+pub const FIRST_PLANET_HOOP_PRICES: [Price; 8] = [
+    Price::loot(1),
+    Price::loot(5),
+    Price::loot(10),
+    Price::loot(20),
+    Price::loot(30),
+    Price::loot(50),
+    Price::loot(100),
+    Price::loot(200),
+];
+/// For the planets unlocked in the galaxy, where the last hoops need stardust and ice. Loops start
+/// with a hoop, so like every hoop price list the last price is never charged
+pub const GALAXY_HOOP_PRICES: [Price; 8] = [
+    Price::loot(1),
+    Price::loot(5),
+    Price::loot(10),
+    Price::loot(20),
+    Price::loot(30),
+    Price::loot(50).with(Stardust, 10),
+    Price::loot(100).with(Ice, 10),
+    Price::loot(200),
+];
+pub const PLANET_PRICES: [Price; 6] = [
+    Price::loot(0),
+    Price::loot(20),
+    Price::loot(40),
+    Price::loot(80).with(Stardust, 15),
+    Price::loot(200),
+    Price::loot(300).with(Stardust, 30).with(Ice, 15),
+];
+/// In the same order as transfer_lanes::LANES
+pub const LANE_PRICES: [Price; 6] = [
+    Price::loot(50),
+    Price::loot(80),
+    Price::loot(120).with(Stardust, 10),
+    Price::loot(200).with(Stardust, 20),
+    Price::loot(300).with(Ice, 10),
+    Price::loot(500).with(Stardust, 20).with(Ice, 20),
+];
@@ -1,3 +1,4 @@
+use crate::game_error::{GameError, report};
 use bevy::prelude::*;
 use bevy_tweening::*;
 
@@ -19,7 +20,7 @@ impl Lens<Projection> for ProjectionScaleLens {
                 perspective.scale = value;
             }
             _ => {
-                panic!("Transitioning for non-orthographic views not supported");
+                report(GameError::NonOrthographicProjection);
             }
         }
     }
@@ -0,0 +1,378 @@
+/// A short list of objectives guiding new players, done one at a time in order. Progress is counted
+/// from gameplay events, or read off the loops for goals about how far the player got, and each
+/// quest pays a bit of loot when done. Nothing else about a game is saved, so neither is the list,
+/// and every launch starts it over along with the game
+use crate::game_error::notify;
+use crate::gameplay_events::{PerfectLoop, PurchaseKind, Purchased, QuestCompleted};
+use crate::hoops_boops_loops::{Loop, Planet};
+use bevy::prelude::*;
+
+const PANEL_WIDTH: f32 = 260.;
+const BAR_COLOR: Color = Color::srgb(0.333, 0.808, 0.929);
+const DONE_ALPHA: f32 = 0.4;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Goal {
+    /// Hoops bought on any planets while the quest is the current one
+    BuyHoops(u32),
+    /// Boops bought on any planets while the quest is the current one
+    BuyBoops(u32),
+    /// Lanes built while the quest is the current one
+    BuildLanes(u32),
+    /// Perfect loops while the quest is the current one
+    PerfectLoops(u32),
+    /// Hoops on the planet with the most
+    HoopsOnAPlanet(u32),
+    UnlockPlanet(Planet),
+    /// Planets unlocked, counting the first one
+    UnlockPlanets(u32),
+}
+
+impl Goal {
+    /// The progress that completes it
+    pub fn target(&self) -> u32 {
+        match self {
+            Goal::BuyHoops(n)
+            | Goal::BuyBoops(n)
+            | Goal::BuildLanes(n)
+            | Goal::PerfectLoops(n)
+            | Goal::HoopsOnAPlanet(n)
+            | Goal::UnlockPlanets(n) => *n,
+            Goal::UnlockPlanet(_) => 1,
+        }
+    }
+
+    /// For goals about how far the player got instead of what they did during the quest, the
+    /// progress the loops show. These count things done before the quest too
+    fn progress_from_loops<'a>(&self, loops: impl Iterator<Item = &'a Loop>) -> Option<u32> {
+        match self {
+            Goal::HoopsOnAPlanet(_) => Some(
+                loops
+                    .map(|r#loop| r#loop.hoop_count() as u32)
+                    .max()
+                    .unwrap_or(0),
+            ),
+            Goal::UnlockPlanet(planet) => Some(
+                loops
+                    .filter(|r#loop| r#loop.planet() == *planet)
+                    .count()
+                    .min(1) as u32,
+            ),
+            Goal::UnlockPlanets(_) => Some(loops.count() as u32),
+            _ => None,
+        }
+    }
+}
+
+pub struct Quest {
+    pub description: &'static str,
+    pub goal: Goal,
+    /// Loot paid on completion
+    pub reward: i32,
+}
+
+/// Every quest, in the order they're done
+pub const QUESTS: [Quest; 8] = [
+    Quest {
+        description: "Buy your first hoop",
+        goal: Goal::BuyHoops(1),
+        reward: 5,
+    },
+    Quest {
+        description: "Buy 3 boops",
+        goal: Goal::BuyBoops(3),
+        reward: 10,
+    },
+    Quest {
+        description: "Reach 8 hoops",
+        goal: Goal::HoopsOnAPlanet(8),
+        reward: 25,
+    },
+    Quest {
+        description: "Unlock planet 2",
+        goal: Goal::UnlockPlanet(Planet::Two),
+        reward: 20,
+    },
+    Quest {
+        description: "Pass a boop through every hoop of a lap in a row",
+        goal: Goal::PerfectLoops(1),
+        reward: 30,
+    },
+    Quest {
+        description: "Unlock planet 3",
+        goal: Goal::UnlockPlanet(Planet::Three),
+        reward: 40,
+    },
+    Quest {
+        description: "Build a lane between two planets",
+        goal: Goal::BuildLanes(1),
+        reward: 60,
+    },
+    Quest {
+        description: "Unlock every planet",
+        goal: Goal::UnlockPlanets(6),
+        reward: 100,
+    },
+];
+
+/// Where the player is in QUESTS
+#[derive(Resource, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct QuestLog {
+    /// Index into QUESTS, every quest is done once it's past the end
+    pub current: usize,
+    /// Toward the current quest's Goal::target
+    pub progress: u32,
+}
+
+impl QuestLog {
+    pub fn current_quest(&self) -> Option<&'static Quest> {
+        QUESTS.get(self.current)
+    }
+}
+
+/// Marker struct for the quest panel
+#[derive(Component)]
+struct QuestPanel;
+
+/// Marker struct for the text at the top of the quest panel, clicking it collapses the panel
+#[derive(Component)]
+struct QuestPanelHeader;
+
+/// The quests in the quest panel, hidden while it's collapsed
+#[derive(Component)]
+struct QuestList {
+    collapsed: bool,
+}
+
+pub fn plugin(app: &mut App) {
+    app.init_resource::<QuestLog>()
+        .add_systems(Startup, spawn_quest_panel)
+        .add_systems(FixedUpdate, complete_quests)
+        .add_systems(
+            Update,
+            (
+                show_quest_panel.run_if(any_with_component::<Loop>),
+                toggle_quest_panel_on_hotkey,
+                update_quest_panel.run_if(resource_changed::<QuestLog>),
+            )
+                .chain(),
+        )
+        .add_observer(progress_on_purchase)
+        .add_observer(progress_on_perfect_loop);
+}
+
+fn progress_on_purchase(t: Trigger<Purchased>, mut log: ResMut<QuestLog>) {
+    let counts = match log.current_quest().map(|quest| quest.goal) {
+        Some(Goal::BuyHoops(_)) => t.kind == PurchaseKind::Hoop,
+        Some(Goal::BuyBoops(_)) => t.kind == PurchaseKind::Boop,
+        Some(Goal::BuildLanes(_)) => t.kind == PurchaseKind::Lane,
+        _ => false,
+    };
+
+    if counts {
+        log.progress += 1;
+    }
+}
+
+fn progress_on_perfect_loop(_: Trigger<PerfectLoop>, mut log: ResMut<QuestLog>) {
+    if let Some(Goal::PerfectLoops(_)) = log.current_quest().map(|quest| quest.goal) {
+        log.progress += 1;
+    }
+}
+
+/// Catches the progress of goals read off the loops up, then moves on to the next quest once the
+/// current one is done, possibly several in one tick
+fn complete_quests(mut log: ResMut<QuestLog>, loop_q: Query<&Loop>, mut commands: Commands) {
+    while let Some(quest) = log.current_quest() {
+        if let Some(progress) = quest.goal.progress_from_loops(loop_q.iter()) {
+            let current = log.current;
+            log.set_if_neq(QuestLog { current, progress });
+        }
+        if log.progress < quest.goal.target() {
+            return;
+        }
+
+        commands.trigger(QuestCompleted {
+            quest: log.current,
+            reward: quest.reward,
+        });
+        notify(format!(
+            "Quest done: {}! +{} loot",
+            quest.description, quest.reward
+        ));
+        *log = QuestLog {
+            current: log.current + 1,
+            progress: 0,
+        };
+    }
+}
+
+fn spawn_quest_panel(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let font = asset_server.load("SpaceGrotesk-Light.ttf");
+
+    commands
+        .spawn((
+            QuestPanel,
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(30.),
+                left: Val::Px(30.),
+                width: Val::Px(PANEL_WIDTH),
+                flex_direction: FlexDirection::Column,
+                padding: UiRect::all(Val::Px(14.)),
+                row_gap: Val::Px(10.),
+                border: UiRect::all(Val::Px(1.)),
+                ..default()
+            },
+            BackgroundColor(Color::WHITE.with_alpha(0.1)),
+            BorderColor(Color::BLACK),
+            BorderRadius::all(Val::Px(20.)),
+            Visibility::Hidden,
+        ))
+        .with_children(|panel| {
+            panel
+                .spawn((
+                    QuestPanelHeader,
+                    Text::default(),
+                    TextFont {
+                        font,
+                        font_size: 24.,
+                        ..default()
+                    },
+                ))
+                .observe(toggle_quest_panel_on_click);
+            panel.spawn((
+                QuestList { collapsed: false },
+                Node {
+                    flex_direction: FlexDirection::Column,
+                    row_gap: Val::Px(12.),
+                    ..default()
+                },
+                Pickable::IGNORE,
+            ));
+        });
+}
+
+/// The panel waits for the titlescreen to be gone
+fn show_quest_panel(mut panel: Single<&mut Visibility, With<QuestPanel>>) {
+    if **panel == Visibility::Hidden {
+        **panel = Visibility::Inherited;
+    }
+}
+
+fn toggle_quest_panel_on_click(
+    mut t: Trigger<Pointer<Click>>,
+    list: Single<(&mut QuestList, &mut Node)>,
+    header: Single<&mut Text, With<QuestPanelHeader>>,
+    log: Res<QuestLog>,
+) {
+    t.propagate(false);
+    toggle_quest_panel(list, header, &log);
+}
+
+/// Q collapses or expands the quest panel
+fn toggle_quest_panel_on_hotkey(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    list: Single<(&mut QuestList, &mut Node)>,
+    header: Single<&mut Text, With<QuestPanelHeader>>,
+    log: Res<QuestLog>,
+) {
+    if keyboard.just_pressed(KeyCode::KeyQ) {
+        toggle_quest_panel(list, header, &log);
+    }
+}
+
+fn toggle_quest_panel(
+    mut list: Single<(&mut QuestList, &mut Node)>,
+    mut header: Single<&mut Text, With<QuestPanelHeader>>,
+    log: &QuestLog,
+) {
+    let (list, node) = &mut *list;
+    list.collapsed = !list.collapsed;
+    node.display = if list.collapsed {
+        Display::None
+    } else {
+        Display::Flex
+    };
+    header.0 = header_text(log, list.collapsed);
+}
+
+fn header_text(log: &QuestLog, collapsed: bool) -> String {
+    let done = log.current.min(QUESTS.len());
+    let toggle = if collapsed { "[+]" } else { "[-]" };
+    format!("Quests {done}/{}  {toggle}", QUESTS.len())
+}
+
+/// Respawns the list with every quest done so far, faded, and the current one with its progress
+fn update_quest_panel(
+    log: Res<QuestLog>,
+    list: Single<(Entity, &QuestList)>,
+    mut header: Single<&mut Text, With<QuestPanelHeader>>,
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+) {
+    let font = asset_server.load("SpaceGrotesk-Light.ttf");
+    let (list_entity, list) = *list;
+    header.0 = header_text(&log, list.collapsed);
+
+    commands
+        .entity(list_entity)
+        .despawn_related::<Children>()
+        .with_children(|quests| {
+            for (i, quest) in QUESTS.iter().enumerate().take(log.current + 1) {
+                let done = i < log.current;
+                let target = quest.goal.target();
+                let progress = if done {
+                    target
+                } else {
+                    log.progress.min(target)
+                };
+                let alpha = if done { DONE_ALPHA } else { 1. };
+
+                quests
+                    .spawn(Node {
+                        flex_direction: FlexDirection::Column,
+                        row_gap: Val::Px(4.),
+                        ..default()
+                    })
+                    .with_children(|row| {
+                        row.spawn((
+                            Text::new(quest.description),
+                            TextFont {
+                                font: font.clone(),
+                                font_size: 18.,
+                                ..default()
+                            },
+                            TextColor(Color::WHITE.with_alpha(alpha)),
+                        ));
+                        row.spawn((
+                            Node {
+                                width: Val::Percent(100.),
+                                height: Val::Px(8.),
+                                ..default()
+                            },
+                            BackgroundColor(Color::WHITE.with_alpha(0.15)),
+                            BorderRadius::MAX,
+                        ))
+                        .with_child((
+                            Node {
+                                width: Val::Percent(100. * progress as f32 / target as f32),
+                                height: Val::Percent(100.),
+                                ..default()
+                            },
+                            BackgroundColor(BAR_COLOR.with_alpha(alpha)),
+                            BorderRadius::MAX,
+                        ));
+                        row.spawn((
+                            Text::new(format!("{progress}/{target}  +{} loot", quest.reward)),
+                            TextFont {
+                                font: font.clone(),
+                                font_size: 14.,
+                                ..default()
+                            },
+                            TextColor(Color::WHITE.with_alpha(alpha * 0.7)),
+                        ));
+                    });
+            }
+        });
+}
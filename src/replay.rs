@@ -0,0 +1,294 @@
+/// Records every player input with the fixed timestep tick it happened on, so sessions can be
+/// played back for bug reports. Playback runs with the recorded seed and one fixed tick per frame,
+/// reproducing the same Loot trajectory
+use crate::focus::Press;
+use crate::game_error::{GameError, GameResult, report};
+use crate::game_rng::GameRng;
+use crate::hoops_boops_loops::Planet;
+use crate::loot::Loot;
+use bevy::prelude::*;
+use std::fmt;
+use std::fs;
+use std::time::Duration;
+
+/// The fixed timestep used both while recording and playing back
+pub const TICK: Duration = Duration::from_micros(15625);
+
+/// Where the current session is written on exit
+pub const LAST_SESSION_PATH: &str = "last-session.replay";
+
+/// Something the player can press, put on the entity that is pressed. Inputs are recorded by
+/// what was pressed instead of by Entity, which would differ between runs
+#[derive(Component, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PlayerInput {
+    /// The titlescreen moon
+    Start,
+    BuyBoop(Planet),
+    BuyHoop(Planet),
+    UnlockPlanet(Planet),
+    /// An unlocked planet's loop
+    ClickPlanet(Planet),
+    /// There's only ever one comet at a time, see galaxy_events.rs
+    CatchComet,
+    /// A boop, by its index in Loop::boops
+    ClickBoop(Planet, usize),
+    /// The unbuilt lane starting at a planet, see transfer_lanes.rs
+    BuildLane(Planet),
+}
+
+impl fmt::Display for PlayerInput {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PlayerInput::Start => write!(f, "start"),
+            PlayerInput::BuyBoop(planet) => write!(f, "boop {}", planet.get_number()),
+            PlayerInput::BuyHoop(planet) => write!(f, "hoop {}", planet.get_number()),
+            PlayerInput::UnlockPlanet(planet) => write!(f, "unlock {}", planet.get_number()),
+            PlayerInput::ClickPlanet(planet) => write!(f, "planet {}", planet.get_number()),
+            PlayerInput::CatchComet => write!(f, "comet"),
+            PlayerInput::ClickBoop(planet, i) => write!(f, "poke {} {i}", planet.get_number()),
+            PlayerInput::BuildLane(planet) => write!(f, "lane {}", planet.get_number()),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct RecordedInput {
+    /// How many fixed ticks had finished when the input happened
+    pub tick: u64,
+    pub input: PlayerInput,
+    /// Loot at the end of that tick, used to notice playback diverging
+    pub loot: i32,
+}
+
+/// A session's seed and inputs. Saved as text, one line per input, like:
+/// ```text
+/// seed 1234
+/// 120 start 0
+/// 900 boop 1 1
+/// ```
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct Replay {
+    pub seed: u64,
+    pub inputs: Vec<RecordedInput>,
+}
+
+impl Replay {
+    pub fn load(path: &str) -> GameResult<Self> {
+        let text = fs::read_to_string(path).map_err(|e| GameError::InvalidReplay(e.to_string()))?;
+        Replay::parse(&text)
+    }
+
+    pub fn save(&self, path: &str) -> GameResult {
+        fs::write(path, self.to_string()).map_err(|e| GameError::InvalidReplay(e.to_string()))
+    }
+
+    pub fn parse(text: &str) -> GameResult<Self> {
+        let invalid = |line: &str| GameError::InvalidReplay(format!("Can't read line `{line}`"));
+
+        let mut lines = text.lines().filter(|line| !line.trim().is_empty());
+
+        let seed_line = lines.next().unwrap_or_default();
+        let seed = seed_line
+            .strip_prefix("seed ")
+            .and_then(|seed| seed.trim().parse().ok())
+            .ok_or_else(|| invalid(seed_line))?;
+
+        let mut inputs = Vec::new();
+        for line in lines {
+            let words: Vec<&str> = line.split_whitespace().collect();
+            let number = |i: usize| -> GameResult<i64> {
+                words
+                    .get(i)
+                    .and_then(|word| word.parse().ok())
+                    .ok_or_else(|| invalid(line))
+            };
+
+            let (input, loot_index) = match words.get(1).copied() {
+                Some("start") => (PlayerInput::Start, 2),
+                Some("boop") => (
+                    PlayerInput::BuyBoop(Planet::from_i32(number(2)? as i32)?),
+                    3,
+                ),
+                Some("hoop") => (
+                    PlayerInput::BuyHoop(Planet::from_i32(number(2)? as i32)?),
+                    3,
+                ),
+                Some("unlock") => (
+                    PlayerInput::UnlockPlanet(Planet::from_i32(number(2)? as i32)?),
+                    3,
+                ),
+                Some("planet") => (
+                    PlayerInput::ClickPlanet(Planet::from_i32(number(2)? as i32)?),
+                    3,
+                ),
+                Some("comet") => (PlayerInput::CatchComet, 2),
+                Some("poke") => (
+                    PlayerInput::ClickBoop(
+                        Planet::from_i32(number(2)? as i32)?,
+                        number(3)? as usize,
+                    ),
+                    4,
+                ),
+                Some("lane") => (
+                    PlayerInput::BuildLane(Planet::from_i32(number(2)? as i32)?),
+                    3,
+                ),
+                _ => return Err(invalid(line)),
+            };
+
+            inputs.push(RecordedInput {
+                tick: number(0)? as u64,
+                input,
+                loot: number(loot_index)? as i32,
+            });
+        }
+
+        Ok(Replay { seed, inputs })
+    }
+}
+
+impl fmt::Display for Replay {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "seed {}", self.seed)?;
+        for recorded in &self.inputs {
+            writeln!(f, "{} {} {}", recorded.tick, recorded.input, recorded.loot)?;
+        }
+        Ok(())
+    }
+}
+
+/// Insert to play back a replay instead of recording. Player input should be turned off, see
+/// main.rs
+#[derive(Resource)]
+pub struct Playback {
+    replay: Replay,
+    /// The index of the next input to play in replay.inputs
+    next: usize,
+}
+
+impl Playback {
+    pub fn new(replay: Replay) -> Self {
+        Playback { replay, next: 0 }
+    }
+
+    pub fn finished(&self) -> bool {
+        self.next >= self.replay.inputs.len()
+    }
+}
+
+/// The inputs of the current session, unless playing back
+#[derive(Resource, Default, Deref)]
+pub struct Recording(Vec<RecordedInput>);
+
+/// Presses aren't recorded while this exists. For presses the player didn't make, like the dev
+/// console's cheats, which would make the replay diverge since the cheat itself isn't recorded
+#[derive(Resource)]
+pub struct RecordingPaused;
+
+/// The number of fixed ticks finished and the loot when the last one finished
+#[derive(Resource, Default)]
+pub struct ReplayClock {
+    pub tick: u64,
+    loot: i32,
+}
+
+pub fn plugin(app: &mut App) {
+    app.insert_resource(Time::<Fixed>::from_duration(TICK))
+        .init_resource::<Recording>()
+        .init_resource::<ReplayClock>()
+        .add_systems(
+            FixedFirst,
+            play_back_inputs.run_if(resource_exists::<Playback>),
+        )
+        .add_systems(FixedLast, advance_replay_clock)
+        .add_systems(
+            Last,
+            save_recording_on_exit.run_if(not(resource_exists::<Playback>)),
+        )
+        .add_observer(record_press);
+}
+
+/// Plays a replay from the start. Seeds GameRng with the replay's seed, so this has to be called
+/// before anything uses it
+pub fn start_playback(app: &mut App, replay: Replay) {
+    app.insert_resource(GameRng::from_seed(replay.seed))
+        .insert_resource(Playback::new(replay));
+}
+
+/// The current session so far, as a Replay
+pub fn recorded_replay(world: &World) -> Replay {
+    Replay {
+        seed: world.resource::<GameRng>().seed(),
+        inputs: world.resource::<Recording>().0.clone(),
+    }
+}
+
+fn advance_replay_clock(mut clock: ResMut<ReplayClock>, loot: Res<Loot>) {
+    clock.tick += 1;
+    clock.loot = **loot;
+}
+
+fn record_press(
+    t: Trigger<Press>,
+    input_q: Query<&PlayerInput>,
+    clock: Res<ReplayClock>,
+    playback: Option<Res<Playback>>,
+    paused: Option<Res<RecordingPaused>>,
+    mut recording: ResMut<Recording>,
+) {
+    if playback.is_some() || paused.is_some() {
+        return;
+    }
+
+    if let Ok(input) = input_q.get(t.target()) {
+        recording.0.push(RecordedInput {
+            tick: clock.tick,
+            input: *input,
+            loot: clock.loot,
+        });
+    }
+}
+
+/// Presses whatever was pressed on this tick while recording
+fn play_back_inputs(
+    mut playback: ResMut<Playback>,
+    clock: Res<ReplayClock>,
+    loot: Res<Loot>,
+    input_q: Query<(Entity, &PlayerInput)>,
+    mut commands: Commands,
+) {
+    while let Some(recorded) = playback.replay.inputs.get(playback.next).copied() {
+        if recorded.tick > clock.tick {
+            break;
+        }
+        playback.next += 1;
+
+        if recorded.loot != **loot {
+            warn!(
+                "Replay diverged on tick {}, recorded {} loot but have {}",
+                recorded.tick, recorded.loot, **loot
+            );
+        }
+
+        match input_q.iter().find(|(_, input)| **input == recorded.input) {
+            Some((entity, _)) => commands.trigger_targets(Press, entity),
+            None => report(GameError::InvalidReplay(format!(
+                "Nothing to press for `{}` on tick {}",
+                recorded.input, recorded.tick
+            ))),
+        }
+    }
+}
+
+fn save_recording_on_exit(world: &mut World) {
+    let exiting = world
+        .get_resource::<Events<AppExit>>()
+        .is_some_and(|events| !events.is_empty());
+
+    if !exiting {
+        return;
+    }
+    if let Err(error) = recorded_replay(world).save(LAST_SESSION_PATH) {
+        warn!("Couldn't save the replay: {error}");
+    }
+}
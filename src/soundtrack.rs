@@ -1,18 +1,300 @@
-use bevy::audio::PlaybackMode;
+/// Music made of stems that play in sync, silent until they're needed. Each phase of the game has
+/// its own theme that is crossfaded to, and some stems of a theme only fade in as the player
+/// progresses. Every stem is a band of frequencies filtered out of soundtrack.ogg as it plays, so
+/// they all come from the one track
+use crate::audio_sink_volume_lens::AudioSinkVolumeLens;
+use crate::band_pass::BandPass;
+use crate::hoops_boops_loops::Loop;
+use crate::transition_to_all_planets::Transitioned;
+use crate::transition_to_first_planet::FirstPlanet;
+use bevy::audio::{AddAudioSource, AudioPlugin, Decodable, PlaybackMode, Source, Volume};
 use bevy::prelude::*;
+use bevy_tweening::{Animator, Tween};
+use std::time::Duration;
+
+const SOUNDTRACK_PATH: &str = "soundtrack.ogg";
+
+const CROSSFADE_DURATION: Duration = Duration::from_secs(3);
+
+/// How long soundtrack.ogg plays before it loops
+const LOOP_SECONDS: f32 = 20.944;
+
+/// Measured from soundtrack.ogg. The autocorrelation of its onsets peaks at 152 BPM, and exactly
+/// 53 beats fit in its loop at 151.8 BPM. The exact fit keeps the beat grid from drifting away
+/// from the music every time it loops
+pub const BEATS_PER_MINUTE: f32 = 53. * 60. / LOOP_SECONDS;
+
+/// The length of the smallest step on the beat grid, an eighth note
+pub const STEP_SECONDS: f32 = 60. / BEATS_PER_MINUTE / 2.;
+
+/// The range of human hearing, in Hz. A band from one to the other is the whole soundtrack
+const LOWEST: u32 = 20;
+const HIGHEST: u32 = 20_000;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Theme {
+    Titlescreen,
+    FirstPlanet,
+    Galaxy,
+}
+
+/// When a stem of the current theme is audible
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Layer {
+    /// Always, the part the others build on
+    Base,
+    /// Once there are at least this many boops
+    Boops(usize),
+    /// Once at least this many planets are unlocked
+    Planets(usize),
+}
+
+struct StemInfo {
+    theme: Theme,
+    layer: Layer,
+    /// The band of the soundtrack it plays, in Hz
+    low: u32,
+    high: u32,
+}
+
+const STEMS: [StemInfo; 7] = [
+    // Muffled, like it's coming from far away
+    StemInfo {
+        theme: Theme::Titlescreen,
+        layer: Layer::Base,
+        low: LOWEST,
+        high: 500,
+    },
+    StemInfo {
+        theme: Theme::FirstPlanet,
+        layer: Layer::Base,
+        low: LOWEST,
+        high: 2_000,
+    },
+    StemInfo {
+        theme: Theme::FirstPlanet,
+        layer: Layer::Boops(4),
+        low: 2_000,
+        high: HIGHEST,
+    },
+    // Starts from the bass and builds back up to the whole soundtrack
+    StemInfo {
+        theme: Theme::Galaxy,
+        layer: Layer::Base,
+        low: LOWEST,
+        high: 250,
+    },
+    StemInfo {
+        theme: Theme::Galaxy,
+        layer: Layer::Planets(3),
+        low: 250,
+        high: 2_000,
+    },
+    StemInfo {
+        theme: Theme::Galaxy,
+        layer: Layer::Planets(6),
+        low: 2_000,
+        high: 6_000,
+    },
+    StemInfo {
+        theme: Theme::Galaxy,
+        layer: Layer::Boops(24),
+        low: 6_000,
+        high: HIGHEST,
+    },
+];
+
+/// A band of the soundtrack, filtered out of it while it's decoded
+#[derive(Asset, TypePath)]
+pub struct Stem {
+    soundtrack: AudioSource,
+    low: u32,
+    high: u32,
+}
+
+impl Decodable for Stem {
+    type DecoderItem = f32;
+    type Decoder = BandPass<Box<dyn Source<Item = f32> + Send>>;
+
+    fn decoder(&self) -> Self::Decoder {
+        BandPass::new(
+            Box::new(self.soundtrack.decoder().convert_samples()),
+            self.low,
+            self.high,
+        )
+    }
+}
+
+/// Every stem is filtered from this once it's loaded
+#[derive(Resource)]
+struct Soundtrack(Handle<AudioSource>);
+
+/// A stem, playing once the soundtrack is loaded
+#[derive(Component)]
+pub struct StemPlayer {
+    /// Index in STEMS
+    stem: usize,
+    /// Whether it's fading or faded in
+    audible: bool,
+}
+
+impl StemPlayer {
+    pub fn theme(&self) -> Theme {
+        STEMS[self.stem].theme
+    }
+
+    pub fn audible(&self) -> bool {
+        self.audible
+    }
+}
+
+/// When the stems started playing, in Time<Real>. Used to line sounds up with the music
+#[derive(Resource, Default)]
+pub struct BeatClock {
+    start: Option<Duration>,
+}
+
+impl BeatClock {
+    /// How many steps of the beat grid have started by `now`, from Time<Real>::elapsed
+    pub fn step(&self, now: Duration) -> u64 {
+        let since_start = now.saturating_sub(self.start.unwrap_or_default());
+        (since_start.as_secs_f32() / STEP_SECONDS) as u64
+    }
+}
 
 /// Plays the sound track
 pub fn soundtrack_plugin(app: &mut App) {
-    app.add_systems(
-        Startup,
-        |mut commands: Commands, asset_server: Res<AssetServer>| {
-            commands.spawn((
-                AudioPlayer::new(asset_server.load("soundtrack.ogg")),
-                PlaybackSettings {
-                    mode: PlaybackMode::Loop,
-                    ..default()
-                },
-            ));
-        },
-    );
+    // Without an audio device, like in the tests, stems are picked but never played
+    if app.is_plugin_added::<AudioPlugin>() {
+        app.add_audio_source::<Stem>();
+    } else {
+        app.init_asset::<Stem>();
+    }
+
+    app.init_resource::<BeatClock>()
+        .add_systems(Startup, spawn_stems)
+        .add_systems(
+            Update,
+            (
+                play_stems_once_loaded,
+                start_beat_clock,
+                pick_audible_stems,
+                fade_stems,
+            )
+                .chain(),
+        );
+}
+
+fn spawn_stems(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(Soundtrack(asset_server.load(SOUNDTRACK_PATH)));
+    for stem in 0..STEMS.len() {
+        commands.spawn(StemPlayer {
+            stem,
+            audible: false,
+        });
+    }
+}
+
+/// Starts every stem on the same frame so they stay in time with each other
+fn play_stems_once_loaded(
+    stem_q: Query<(Entity, &StemPlayer), Without<AudioPlayer<Stem>>>,
+    soundtrack: Res<Soundtrack>,
+    audio_sources: Res<Assets<AudioSource>>,
+    mut stems: ResMut<Assets<Stem>>,
+    mut commands: Commands,
+) {
+    let Some(source) = audio_sources.get(&soundtrack.0) else {
+        return;
+    };
+
+    for (entity, stem_player) in stem_q {
+        let info = &STEMS[stem_player.stem];
+        let stem = stems.add(Stem {
+            soundtrack: source.clone(),
+            low: info.low,
+            high: info.high,
+        });
+
+        commands.entity(entity).insert((
+            AudioPlayer(stem),
+            PlaybackSettings {
+                mode: PlaybackMode::Loop,
+                volume: Volume::SILENT,
+                ..default()
+            },
+        ));
+    }
+}
+
+/// Starts the clock when the first stem starts playing, they all start on the same frame
+fn start_beat_clock(
+    started_q: Query<(), (With<StemPlayer>, Added<AudioSink>)>,
+    mut beat_clock: ResMut<BeatClock>,
+    time: Res<Time<Real>>,
+) {
+    if beat_clock.start.is_none() && !started_q.is_empty() {
+        beat_clock.start = Some(time.elapsed());
+    }
+}
+
+fn current_theme(transitioned: &Transitioned, first_planet_exists: bool) -> Theme {
+    if **transitioned {
+        Theme::Galaxy
+    } else if first_planet_exists {
+        Theme::FirstPlanet
+    } else {
+        Theme::Titlescreen
+    }
+}
+
+/// Decides which stems should be audible from the theme and the player's progress
+fn pick_audible_stems(
+    stem_q: Query<&mut StemPlayer>,
+    transitioned: Res<Transitioned>,
+    first_planet_q: Query<(), With<FirstPlanet>>,
+    loop_q: Query<&Loop>,
+) {
+    let theme = current_theme(&transitioned, !first_planet_q.is_empty());
+    let boops = loop_q.iter().map(|r#loop| r#loop.boop_count()).sum::<u64>() as usize;
+    let planets = loop_q.iter().count();
+
+    for mut stem_player in stem_q {
+        let stem = &STEMS[stem_player.stem];
+        let audible = stem.theme == theme
+            && match stem.layer {
+                Layer::Base => true,
+                Layer::Boops(min) => boops >= min,
+                Layer::Planets(min) => planets >= min,
+            };
+
+        if audible != stem_player.audible {
+            stem_player.audible = audible;
+        }
+    }
+}
+
+/// Crossfades stems in or out when they start playing or whether they should be audible changes
+fn fade_stems(
+    stem_q: Query<(Entity, Ref<StemPlayer>, Ref<AudioSink>)>,
+    mut commands: Commands,
+) {
+    for (entity, stem_player, sink) in stem_q {
+        if !stem_player.is_changed() && !sink.is_added() {
+            continue;
+        }
+
+        // Starting from wherever the volume is, in case a fade is interrupted
+        let start = sink.volume().to_linear();
+        let end = if stem_player.audible { 1. } else { 0. };
+        if start == end {
+            continue;
+        }
+
+        let fade = Tween::new(
+            EaseFunction::QuadraticInOut,
+            CROSSFADE_DURATION,
+            AudioSinkVolumeLens { start, end },
+        );
+        commands.entity(entity).insert(Animator::new(fade));
+    }
 }
@@ -0,0 +1,21 @@
+/// Pans sounds by where they happen on screen. The camera never moves or zooms, planets are
+/// scaled instead, so world positions are screen positions for both the first planet and the
+/// galaxy
+use crate::screen_size::SCREEN_SIZE;
+use bevy::audio::SpatialScale;
+use bevy::prelude::*;
+
+/// Scales positions so the center of the screen is 0 and its left and right edges are -1 and 1
+const SCREEN_TO_SPATIAL: SpatialScale = SpatialScale(Vec3::splat(2. / SCREEN_SIZE.x));
+
+/// Like PlaybackSettings::DESPAWN, but panned by the entity's Transform
+pub const SPATIAL_DESPAWN: PlaybackSettings = PlaybackSettings {
+    spatial: true,
+    spatial_scale: Some(SCREEN_TO_SPATIAL),
+    ..PlaybackSettings::DESPAWN
+};
+
+/// Put on the camera, with an ear on each edge of the screen
+pub fn listener() -> SpatialListener {
+    SpatialListener::new(SCREEN_SIZE.x)
+}
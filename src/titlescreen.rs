@@ -1,3 +1,5 @@
+use crate::focus::{Focusable, Press};
+use crate::replay::PlayerInput;
 use crate::transition_to_first_planet::TransitionToFirstPlanet;
 use bevy::prelude::*;
 
@@ -20,6 +22,8 @@ pub struct TitlescreenParent;
 /// In World coords
 pub const PLAY_BTN_LOCATION: Vec2 = Vec2::new(-26., 88.);
 
+const INITIAL_MOON_RADIUS: f32 = 60.;
+
 /// Spawns the titlescreen and play btn
 pub fn titlescreen_plugin(app: &mut App) {
     app.add_systems(Startup, spawn_title_screen);
@@ -67,9 +71,14 @@ fn spawn_title_screen(mut commands: Commands, asset_server: Res<AssetServer>) {
                 ..default()
             },
         ))
-        .observe(|_: Trigger<Pointer<Click>>, mut commands: Commands| {
-            commands.queue(TransitionToFirstPlanet);
-        })
+        // Pressing the moon starts the game so it's the same for every input device and replays
+        .observe(
+            |_: Trigger<Pointer<Click>>,
+             moon: Single<Entity, With<TitlescreenMoon>>,
+             mut commands: Commands| {
+                commands.trigger_targets(Press, *moon);
+            },
+        )
         .id();
 
     // Represents the initial moon that turns into the play button on hover
@@ -85,6 +94,12 @@ fn spawn_title_screen(mut commands: Commands, asset_server: Res<AssetServer>) {
                 ..default()
             },
             TitlescreenMoon,
+            PlayerInput::Start,
+            // The moon art is full screen, so the focus ring is moved to where it's drawn
+            Focusable {
+                radius: INITIAL_MOON_RADIUS,
+                offset: PLAY_BTN_LOCATION,
+            },
         ))
         .observe(move |_: Trigger<Pointer<Over>>, mut commands: Commands| {
             commands.entity(btn).entry::<Sprite>().and_modify(|mut s| {
@@ -96,6 +111,10 @@ fn spawn_title_screen(mut commands: Commands, asset_server: Res<AssetServer>) {
                 s.color = Color::WHITE.with_alpha(0.);
             });
         })
+        // Clicks are handled by the play btn on top
+        .observe(|_: Trigger<Press>, mut commands: Commands| {
+            commands.queue(TransitionToFirstPlanet);
+        })
         .id();
 
     commands
@@ -0,0 +1,118 @@
+/// A box of text next to the cursor explaining whatever it's over. Anything with a Tooltip shows
+/// it while hovered, once its Pointer<Over> observer calls show_tooltip and its Pointer<Out>
+/// observer calls hide_tooltip. The owner keeps the Tooltip's text up to date while it's hovered
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+
+/// Between the cursor and the tooltip's top left corner
+const CURSOR_OFFSET: Vec2 = Vec2::new(18., 18.);
+const TOOLTIP_WIDTH: f32 = 260.;
+
+/// The text shown while hovering the entity, one line per row
+#[derive(Component, Default, PartialEq, Eq, Debug)]
+pub struct Tooltip(pub String);
+
+/// The entity whose Tooltip is shown, if any
+#[derive(Resource, Default, Deref)]
+pub struct Hovered(Option<Entity>);
+
+/// Marker struct for the tooltip box
+#[derive(Component)]
+struct TooltipBox;
+
+/// Marker struct for the Text in the TooltipBox
+#[derive(Component)]
+struct TooltipBoxText;
+
+pub fn plugin(app: &mut App) {
+    app.init_resource::<Hovered>()
+        .add_systems(Startup, spawn_tooltip_box)
+        .add_systems(Update, update_tooltip_box);
+}
+
+pub fn show_tooltip(entity: Entity, hovered: &mut Hovered) {
+    hovered.0 = Some(entity);
+}
+
+/// Only hides the tooltip if it's `entity`'s, so moving straight from one thing to another doesn't
+/// hide the second's
+pub fn hide_tooltip(entity: Entity, hovered: &mut Hovered) {
+    if hovered.0 == Some(entity) {
+        hovered.0 = None;
+    }
+}
+
+fn spawn_tooltip_box(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands
+        .spawn((
+            TooltipBox,
+            Node {
+                display: Display::None,
+                position_type: PositionType::Absolute,
+                max_width: Val::Px(TOOLTIP_WIDTH),
+                padding: UiRect::axes(Val::Px(12.), Val::Px(8.)),
+                border: UiRect::all(Val::Px(1.)),
+                ..default()
+            },
+            BackgroundColor(Color::BLACK.with_alpha(0.8)),
+            BorderColor(Color::WHITE),
+            BorderRadius::all(Val::Px(10.)),
+            GlobalZIndex(20),
+            Pickable::IGNORE,
+        ))
+        .with_child((
+            TooltipBoxText,
+            Text::default(),
+            TextFont {
+                font: asset_server.load("SpaceGrotesk-Light.ttf"),
+                font_size: 16.,
+                ..default()
+            },
+            Pickable::IGNORE,
+        ));
+}
+
+/// Shows the hovered entity's Tooltip next to the cursor, kept on screen
+fn update_tooltip_box(
+    mut hovered: ResMut<Hovered>,
+    tooltip_q: Query<&Tooltip>,
+    window_q: Query<&Window, With<PrimaryWindow>>,
+    tooltip_box: Single<(&mut Node, &ComputedNode), With<TooltipBox>>,
+    mut text: Single<&mut Text, With<TooltipBoxText>>,
+) {
+    let (mut node, computed) = tooltip_box.into_inner();
+
+    // Forget entities despawned while hovered, like a locked planet that was just bought
+    let tooltip = hovered.and_then(|entity| tooltip_q.get(entity).ok());
+    if tooltip.is_none() && hovered.is_some() {
+        hovered.0 = None;
+    }
+    let cursor = window_q
+        .single()
+        .ok()
+        .and_then(|window| window.cursor_position());
+
+    // Owners may only fill in the text once it's hovered, so an empty one isn't shown yet
+    let tooltip = tooltip.filter(|tooltip| !tooltip.0.is_empty());
+    let (Some(tooltip), Some(cursor)) = (tooltip, cursor) else {
+        node.display = Display::None;
+        return;
+    };
+
+    if text.0 != tooltip.0 {
+        text.0 = tooltip.0.clone();
+    }
+
+    let window_size = window_q
+        .single()
+        .map(|window| window.size())
+        .unwrap_or_default();
+    let size = computed.size() * computed.inverse_scale_factor();
+    let position = (cursor + CURSOR_OFFSET)
+        .min(window_size - size)
+        .max(Vec2::ZERO);
+
+    node.display = Display::Flex;
+    node.left = Val::Px(position.x);
+    node.top = Val::Px(position.y);
+}
@@ -0,0 +1,292 @@
+/// Lanes between neighbouring planets in the galaxy. Once both ends are unlocked a lane can be
+/// built, and from then on every few laps it takes a boop from its first planet, carries it along
+/// an arc and drops it into the second, paying a bonus on arrival. The lanes go around the galaxy
+/// in a ring, so boops can keep travelling from planet to planet
+use crate::focus::{Focusable, Press, press_on_click};
+use crate::game_error::{GameError, report};
+use crate::gameplay_events::{BoopTransferred, PlanetUnlocked, PurchaseKind, Purchased};
+use crate::hoops_boops_loops::{AddBoop, BOOP_SPEED, Loop, Planet, RemoveBoop};
+use crate::prices::LANE_PRICES;
+use crate::replay::PlayerInput;
+use crate::scales::ZOOMED_OUT_PLANET_SCALE;
+use crate::spatial_audio::SPATIAL_DESPAWN;
+use crate::wallet::Funds;
+use bevy::color::palettes::basic::BLACK;
+use bevy::prelude::*;
+use std::f32::consts::PI;
+use std::time::Duration;
+
+/// Every lane, from its first planet to its second. Clockwise around the two rows of planets
+const LANES: [(Planet, Planet); 6] = [
+    (Planet::One, Planet::Two),
+    (Planet::Two, Planet::Three),
+    (Planet::Three, Planet::Six),
+    (Planet::Six, Planet::Five),
+    (Planet::Five, Planet::Four),
+    (Planet::Four, Planet::One),
+];
+
+/// A lane takes a boop every this many laps
+const LANE_LAPS: f32 = 3.;
+const TRAVEL_DURATION: Duration = Duration::from_secs(4);
+/// Loot for every boop that makes it to the other end
+pub const LANE_BONUS: i32 = 5;
+
+/// How far the arc bends away from a straight line, relative to its length
+const ARC_BEND: f32 = 0.2;
+/// Sprites the arc is drawn with
+const ARC_DOTS: usize = 24;
+const UNBUILT_ARC_COLOR: Color = Color::srgba(1., 1., 1., 0.08);
+const BUILT_ARC_COLOR: Color = Color::srgba(1., 1., 1., 0.35);
+const LANE_BTN_FOCUS_RADIUS: f32 = 30.;
+const TRAVELLER_SCALE: f32 = 0.1 * ZOOMED_OUT_PLANET_SCALE;
+
+/// A lane that can be or has been built, index into LANES
+#[derive(Component)]
+struct Lane {
+    index: usize,
+    from: Entity,
+    to: Entity,
+    /// The dots the arc is drawn with
+    dots: Vec<Entity>,
+    /// The price tag, until it's built
+    price_text: Entity,
+}
+
+/// On a built Lane, counts down to taking the next boop
+#[derive(Component)]
+struct LaneDeparture(Timer);
+
+/// A boop on its way along a lane
+#[derive(Component)]
+struct Traveller {
+    lane: Entity,
+    timer: Timer,
+}
+
+pub fn plugin(app: &mut App) {
+    app.add_systems(
+        FixedUpdate,
+        (send_boops_down_lanes, move_travellers).chain(),
+    )
+    .add_observer(spawn_lanes_on_planet_unlocked);
+}
+
+/// Where on the arc of a lane from `from` to `to` a boop is `fraction` of the way along
+fn lane_point(from: Vec2, to: Vec2, fraction: f32) -> Vec2 {
+    let bend = (to - from).perp() * ARC_BEND;
+    let control = from.midpoint(to) + bend;
+    from.lerp(control, fraction)
+        .lerp(control.lerp(to, fraction), fraction)
+}
+
+/// Every lane whose planets are both unlocked now gets spawned, unbuilt, with a price tag in the
+/// middle of its arc
+fn spawn_lanes_on_planet_unlocked(
+    _: Trigger<PlanetUnlocked>,
+    loop_q: Query<(Entity, &Loop, &Transform)>,
+    lane_q: Query<&Lane>,
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+) {
+    let find_loop = |planet: Planet| {
+        loop_q
+            .iter()
+            .find(|(_, r#loop, _)| r#loop.planet() == planet)
+            .map(|(entity, _, transform)| (entity, transform.translation.truncate()))
+    };
+
+    for (index, (from_planet, to_planet)) in LANES.iter().enumerate() {
+        if lane_q.iter().any(|lane| lane.index == index) {
+            continue;
+        }
+        let (Some((from, from_position)), Some((to, to_position))) =
+            (find_loop(*from_planet), find_loop(*to_planet))
+        else {
+            continue;
+        };
+
+        let dots = (1..=ARC_DOTS)
+            .map(|i| {
+                let fraction = i as f32 / (ARC_DOTS + 1) as f32;
+                commands
+                    .spawn((
+                        Sprite::from_color(UNBUILT_ARC_COLOR, Vec2::splat(4.)),
+                        Transform::from_translation(
+                            lane_point(from_position, to_position, fraction).extend(-5.),
+                        ),
+                        Pickable::IGNORE,
+                    ))
+                    .id()
+            })
+            .collect();
+
+        let middle = lane_point(from_position, to_position, 0.5);
+        let price = LANE_PRICES[index];
+        let price_label = format!("{} {}", price.loot, price.extras_label());
+        let price_text = commands
+            .spawn((
+                Text2d::new(price_label.trim_end()),
+                TextFont {
+                    font: asset_server.load("SpaceGrotesk-Light.ttf"),
+                    font_size: 40.,
+                    ..default()
+                },
+                TextColor(BLACK.into()),
+                Transform::from_xyz(0., 0., 1.),
+                Pickable::IGNORE,
+            ))
+            .id();
+
+        commands
+            .spawn((
+                Lane {
+                    index,
+                    from,
+                    to,
+                    dots,
+                    price_text,
+                },
+                Sprite::from_image(asset_server.load("moon-btn.png")),
+                Transform {
+                    translation: middle.extend(-4.),
+                    scale: Vec3::splat(ZOOMED_OUT_PLANET_SCALE),
+                    ..default()
+                },
+                Pickable::default(),
+                Focusable::new(LANE_BTN_FOCUS_RADIUS),
+                PlayerInput::BuildLane(*from_planet),
+            ))
+            .add_child(price_text)
+            .observe(press_on_click)
+            .observe(build_lane_on_press);
+    }
+}
+
+fn build_lane_on_press(
+    t: Trigger<Press>,
+    lane_q: Query<(&Lane, &Transform), Without<LaneDeparture>>,
+    mut funds: Funds,
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+) {
+    let Ok((lane, transform)) = lane_q.get(t.target()) else {
+        return;
+    };
+
+    let price = LANE_PRICES[lane.index];
+    if !funds.spend(&price) {
+        commands.spawn((
+            AudioPlayer::new(asset_server.load("unsuccessful-buy.ogg")),
+            *transform,
+            SPATIAL_DESPAWN,
+        ));
+        return;
+    }
+
+    let lap = Duration::from_secs_f32(2. * PI / BOOP_SPEED);
+    commands
+        .entity(t.target())
+        .insert(LaneDeparture(Timer::new(
+            lap.mul_f32(LANE_LAPS),
+            TimerMode::Repeating,
+        )))
+        .remove::<(Focusable, PlayerInput)>()
+        .insert((Visibility::Hidden, Pickable::IGNORE));
+    commands.entity(lane.price_text).despawn();
+    for dot in &lane.dots {
+        commands
+            .entity(*dot)
+            .entry::<Sprite>()
+            .and_modify(|mut sprite| sprite.color = BUILT_ARC_COLOR);
+    }
+
+    commands.trigger(Purchased {
+        kind: PurchaseKind::Lane,
+        r#loop: lane.from,
+        price,
+    });
+}
+
+/// Planets are never emptied, a lane waits while its first planet is down to one boop
+fn send_boops_down_lanes(
+    lane_q: Query<(Entity, &Lane, &mut LaneDeparture)>,
+    loop_q: Query<(&Loop, &Transform)>,
+    time: Res<Time>,
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+) {
+    for (lane_entity, lane, mut departure) in lane_q {
+        if !departure.0.tick(time.delta()).just_finished() {
+            continue;
+        }
+        let Ok((r#loop, transform)) = loop_q.get(lane.from) else {
+            report(GameError::MissingEntity(lane.from));
+            continue;
+        };
+        if r#loop.boop_count() <= 1 {
+            continue;
+        }
+
+        commands.queue(RemoveBoop(lane.from));
+        commands.spawn((
+            Traveller {
+                lane: lane_entity,
+                timer: Timer::new(TRAVEL_DURATION, TimerMode::Once),
+            },
+            Sprite::from_image(asset_server.load("boop.png")),
+            Transform {
+                translation: transform.translation.truncate().extend(3.),
+                scale: Vec3::splat(TRAVELLER_SCALE),
+                ..default()
+            },
+            Pickable::IGNORE,
+        ));
+    }
+}
+
+fn move_travellers(
+    traveller_q: Query<(Entity, &mut Traveller, &mut Transform)>,
+    lane_q: Query<&Lane>,
+    loop_q: Query<(&Loop, &Transform), Without<Traveller>>,
+    time: Res<Time>,
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+) {
+    for (entity, mut traveller, mut transform) in traveller_q {
+        let ends = lane_q
+            .get(traveller.lane)
+            .ok()
+            .and_then(|lane| Some((loop_q.get(lane.from).ok()?, loop_q.get(lane.to).ok()?, lane)));
+        let Some(((from, from_transform), (to, to_transform), lane)) = ends else {
+            commands.entity(entity).despawn();
+            continue;
+        };
+
+        traveller.timer.tick(time.delta());
+        let point = lane_point(
+            from_transform.translation.truncate(),
+            to_transform.translation.truncate(),
+            traveller.timer.fraction(),
+        );
+        transform.translation = point.extend(transform.translation.z);
+
+        if traveller.timer.finished() {
+            commands.entity(entity).despawn();
+            commands.queue(AddBoop(lane.to));
+            commands.trigger(BoopTransferred {
+                from: from.planet(),
+                to: to.planet(),
+                bonus: LANE_BONUS,
+            });
+            commands.spawn((
+                AudioPlayer::new(asset_server.load("boop-going-through-hoop.ogg")),
+                Transform::from_translation(to_transform.translation),
+                PlaybackSettings {
+                    speed: 0.75,
+                    ..SPATIAL_DESPAWN
+                },
+            ));
+        }
+    }
+}
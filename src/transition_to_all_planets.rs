@@ -1,5 +1,6 @@
 mod tweens;
 
+use crate::game_error::report;
 use crate::hoops_boops_loops::{AllHoopsBought, Planet};
 use crate::locked_planets::{LockedPlanet, SpawnLockedPlanet};
 use crate::screen_size::SCREEN_SIZE;
@@ -8,8 +9,9 @@ use bevy::prelude::*;
 use bevy_tweening::Animator;
 use tweens::*;
 
+/// Whether the galaxy with all the planets has been transitioned to
 #[derive(Resource, Deref, DerefMut)]
-struct Transitioned(bool);
+pub struct Transitioned(bool);
 
 pub fn plugin(app: &mut App) {
     app.insert_resource(Transitioned(false))
@@ -33,11 +35,14 @@ fn transition_to_all_planets(
     ));
 
     for i in 1..PLANET_COUNT {
-        commands.queue(SpawnLockedPlanet {
-            pos: planet_positions[i as usize],
-            initial_scale: 0.,
-            planet: Planet::from_i32(i + 1),
-        });
+        match Planet::from_i32(i + 1) {
+            Ok(planet) => commands.queue(SpawnLockedPlanet {
+                pos: planet_positions[i as usize],
+                initial_scale: 0.,
+                planet,
+            }),
+            Err(error) => report(error),
+        }
     }
 
     fn calculate_planet_positions() -> [Vec2; 6] {
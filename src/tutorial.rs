@@ -0,0 +1,394 @@
+/// A tutorial for the first planet, only on the first launch. It dims the screen except for a
+/// spotlight on one thing at a time, the boop moon, the hoop moon and then the loot display, with
+/// a callout explaining it. Each step waits for the purchase it asks for, and it can be skipped at
+/// any time. It's only an overlay, clicks go through it and nothing it does changes the game
+use crate::game_error::{GameError, GameResult, report};
+use crate::gameplay_events::{PurchaseKind, Purchased};
+use crate::hoops_boops_loops::Planet;
+use crate::loot::LootDisplay;
+use crate::replay::PlayerInput;
+use crate::screen_size::SCREEN_SIZE;
+use bevy::prelude::*;
+use std::fs;
+use std::path::Path;
+
+/// Exists once the tutorial was finished or skipped, see main.rs
+pub const TUTORIAL_SAVE_PATH: &str = "tutorial.save";
+
+/// Everything besides the spotlight gets this much darker
+const DIM_COLOR: Color = Color::srgba(0., 0., 0., 0.6);
+const CALLOUT_COLOR: Color = Color::srgb(0.333, 0.808, 0.929);
+const CALLOUT_WIDTH: f32 = 320.;
+/// Between the spotlight and its target, and between the spotlight and the callout
+const SPOTLIGHT_MARGIN: f32 = 20.;
+const CALLOUT_GAP: f32 = 40.;
+/// In world units, matches the moon sprites
+const MOON_RADIUS: f32 = 65.;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Target {
+    BoopMoon,
+    HoopMoon,
+    LootDisplay,
+}
+
+struct Step {
+    target: Target,
+    text: &'static str,
+    /// The purchase that moves on to the next step, None for any
+    advances_on: Option<PurchaseKind>,
+}
+
+const STEPS: [Step; 3] = [
+    Step {
+        target: Target::BoopMoon,
+        text: "This moon buys boops. Boops orbit the planet and earn loot every time they pass \
+               through a hoop. Click it, or press 1, once you have the loot",
+        advances_on: Some(PurchaseKind::Boop),
+    },
+    Step {
+        target: Target::HoopMoon,
+        text: "This one buys hoops. More hoops means more loot from every lap. Click it, or \
+               press 2",
+        advances_on: Some(PurchaseKind::Hoop),
+    },
+    Step {
+        target: Target::LootDisplay,
+        text: "Your loot is counted here. Fill the planet with 8 hoops to reach the rest of the \
+               galaxy. Buy anything to finish the tutorial",
+        advances_on: None,
+    },
+];
+
+/// Insert Tutorial::Step(0) to run the tutorial, see main.rs
+#[derive(Resource, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum Tutorial {
+    /// Not running. The default, so tests and replays don't get it
+    #[default]
+    Off,
+    /// Showing STEPS[i]
+    Step(usize),
+}
+
+/// Insert to write TUTORIAL_SAVE_PATH as soon as the tutorial is over, so it isn't shown again even
+/// if the game is killed instead of closed. Only main.rs does, so tests don't write saves
+#[derive(Resource)]
+pub struct SaveTutorialDone;
+
+/// Whether the tutorial was finished or skipped on an earlier launch
+pub fn load_tutorial_done(path: &str) -> GameResult<bool> {
+    Path::new(path)
+        .try_exists()
+        .map_err(|e| GameError::InvalidSave(e.to_string()))
+}
+
+/// Marker struct for the parent of everything in the tutorial overlay
+#[derive(Component)]
+struct TutorialOverlay;
+
+/// The parts of the dim mask around the spotlight
+#[derive(Component, Clone, Copy)]
+enum DimSide {
+    Top,
+    Bottom,
+    Left,
+    Right,
+}
+
+/// Marker struct for the box explaining the current step
+#[derive(Component)]
+struct Callout;
+
+/// Marker struct for the Text in the Callout
+#[derive(Component)]
+struct CalloutText;
+
+/// Marker struct for the line from the callout to the spotlight
+#[derive(Component)]
+struct CalloutPointer;
+
+pub fn plugin(app: &mut App) {
+    app.init_resource::<Tutorial>()
+        .add_systems(
+            Update,
+            (
+                skip_tutorial_on_hotkey,
+                update_tutorial_overlay.run_if(resource_changed::<Tutorial>),
+                place_tutorial_overlay,
+                save_tutorial_done
+                    .run_if(resource_exists::<SaveTutorialDone>.and(resource_changed::<Tutorial>)),
+            )
+                .chain(),
+        )
+        .add_observer(advance_tutorial_on_purchase);
+}
+
+fn advance_tutorial_on_purchase(t: Trigger<Purchased>, mut tutorial: ResMut<Tutorial>) {
+    let Tutorial::Step(i) = *tutorial else {
+        return;
+    };
+
+    if STEPS[i].advances_on.is_none_or(|kind| kind == t.kind) {
+        *tutorial = if i + 1 < STEPS.len() {
+            Tutorial::Step(i + 1)
+        } else {
+            Tutorial::Off
+        };
+    }
+}
+
+/// Escape skips the tutorial, like the skip button
+fn skip_tutorial_on_hotkey(keyboard: Res<ButtonInput<KeyCode>>, mut tutorial: ResMut<Tutorial>) {
+    if keyboard.just_pressed(KeyCode::Escape) && *tutorial != Tutorial::Off {
+        *tutorial = Tutorial::Off;
+    }
+}
+
+fn save_tutorial_done(tutorial: Res<Tutorial>) {
+    if *tutorial != Tutorial::Off {
+        return;
+    }
+
+    if let Err(e) = fs::write(TUTORIAL_SAVE_PATH, "done\n") {
+        report(GameError::InvalidSave(e.to_string()));
+    }
+}
+
+fn skip_tutorial_on_click(mut t: Trigger<Pointer<Click>>, mut tutorial: ResMut<Tutorial>) {
+    t.propagate(false);
+    *tutorial = Tutorial::Off;
+}
+
+/// Spawns the overlay when the tutorial starts, changes the callout's text on every step and
+/// despawns the overlay once it's over
+fn update_tutorial_overlay(
+    tutorial: Res<Tutorial>,
+    overlay_q: Query<Entity, With<TutorialOverlay>>,
+    text_q: Query<&mut Text, With<CalloutText>>,
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+) {
+    let Tutorial::Step(i) = *tutorial else {
+        for overlay in overlay_q {
+            commands.entity(overlay).despawn();
+        }
+        return;
+    };
+
+    if overlay_q.is_empty() {
+        spawn_tutorial_overlay(STEPS[i].text, &mut commands, &asset_server);
+    }
+    for mut text in text_q {
+        text.0 = STEPS[i].text.to_string();
+    }
+}
+
+fn spawn_tutorial_overlay(text: &str, commands: &mut Commands, asset_server: &AssetServer) {
+    let font = asset_server.load("SpaceGrotesk-Light.ttf");
+
+    commands
+        .spawn((
+            TutorialOverlay,
+            Node {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.),
+                height: Val::Percent(100.),
+                ..default()
+            },
+            GlobalZIndex(10),
+            // Hidden until the first target is on screen
+            Visibility::Hidden,
+            Pickable::IGNORE,
+        ))
+        .with_children(|overlay| {
+            for side in [DimSide::Top, DimSide::Bottom, DimSide::Left, DimSide::Right] {
+                overlay.spawn((
+                    side,
+                    Node {
+                        position_type: PositionType::Absolute,
+                        ..default()
+                    },
+                    BackgroundColor(DIM_COLOR),
+                    Pickable::IGNORE,
+                ));
+            }
+
+            overlay.spawn((
+                CalloutPointer,
+                Node {
+                    position_type: PositionType::Absolute,
+                    width: Val::Px(2.),
+                    ..default()
+                },
+                BackgroundColor(CALLOUT_COLOR),
+                Pickable::IGNORE,
+            ));
+
+            overlay
+                .spawn((
+                    Callout,
+                    Node {
+                        position_type: PositionType::Absolute,
+                        width: Val::Px(CALLOUT_WIDTH),
+                        flex_direction: FlexDirection::Column,
+                        padding: UiRect::all(Val::Px(14.)),
+                        row_gap: Val::Px(10.),
+                        border: UiRect::all(Val::Px(1.)),
+                        ..default()
+                    },
+                    BackgroundColor(Color::BLACK.with_alpha(0.8)),
+                    BorderColor(CALLOUT_COLOR),
+                    BorderRadius::all(Val::Px(20.)),
+                    Pickable::IGNORE,
+                ))
+                .with_children(|callout| {
+                    callout.spawn((
+                        CalloutText,
+                        Text::new(text),
+                        TextFont {
+                            font: font.clone(),
+                            font_size: 20.,
+                            ..default()
+                        },
+                        Pickable::IGNORE,
+                    ));
+                    callout
+                        .spawn((
+                            Node {
+                                align_self: AlignSelf::End,
+                                padding: UiRect::axes(Val::Px(14.), Val::Px(6.)),
+                                border: UiRect::all(Val::Px(1.)),
+                                ..default()
+                            },
+                            BackgroundColor(Color::WHITE.with_alpha(0.1)),
+                            BorderColor(Color::WHITE),
+                            BorderRadius::MAX,
+                        ))
+                        .with_child((
+                            Text::new("Skip tutorial"),
+                            TextFont {
+                                font,
+                                font_size: 16.,
+                                ..default()
+                            },
+                            Pickable::IGNORE,
+                        ))
+                        .observe(skip_tutorial_on_click);
+                });
+        });
+}
+
+/// Where the current step's target is on screen, in logical pixels from the top left. None while
+/// it's not on screen, like before the moons have come in
+fn target_rect(
+    target: Target,
+    camera_q: &Query<(&Camera, &GlobalTransform), With<Camera2d>>,
+    input_q: &Query<(&PlayerInput, &GlobalTransform)>,
+    loot_display_q: &Query<(&ComputedNode, &GlobalTransform), With<LootDisplay>>,
+) -> Option<Rect> {
+    let rect = match target {
+        Target::BoopMoon | Target::HoopMoon => {
+            let input = if target == Target::BoopMoon {
+                PlayerInput::BuyBoop(Planet::One)
+            } else {
+                PlayerInput::BuyHoop(Planet::One)
+            };
+            let (_, moon) = input_q.iter().find(|(i, _)| **i == input)?;
+            let (camera, camera_transform) = camera_q.single().ok()?;
+
+            let center = moon.translation();
+            let edge = center + Vec3::X * MOON_RADIUS * moon.scale().x;
+            let center = camera.world_to_viewport(camera_transform, center).ok()?;
+            let edge = camera.world_to_viewport(camera_transform, edge).ok()?;
+            Rect::from_center_half_size(center, Vec2::splat(center.distance(edge)))
+        }
+        Target::LootDisplay => {
+            let (node, transform) = loot_display_q.single().ok()?;
+            let scale = node.inverse_scale_factor();
+            Rect::from_center_size(
+                transform.translation().truncate() * scale,
+                node.size() * scale,
+            )
+        }
+    };
+
+    let screen = Rect::from_corners(Vec2::ZERO, SCREEN_SIZE);
+    (screen.contains(rect.min) && screen.contains(rect.max)).then_some(rect)
+}
+
+/// Moves the spotlight and callout to the current step's target every frame, since the moons
+/// orbit and the loot display slides in
+fn place_tutorial_overlay(
+    tutorial: Res<Tutorial>,
+    camera_q: Query<(&Camera, &GlobalTransform), With<Camera2d>>,
+    input_q: Query<(&PlayerInput, &GlobalTransform)>,
+    loot_display_q: Query<(&ComputedNode, &GlobalTransform), With<LootDisplay>>,
+    mut overlay_q: Query<&mut Visibility, With<TutorialOverlay>>,
+    side_q: Query<(&DimSide, &mut Node)>,
+    mut callout_q: Query<&mut Node, (With<Callout>, Without<DimSide>)>,
+    mut pointer_q: Query<&mut Node, (With<CalloutPointer>, Without<DimSide>, Without<Callout>)>,
+) {
+    let Tutorial::Step(i) = *tutorial else {
+        return;
+    };
+    let Ok(mut visibility) = overlay_q.single_mut() else {
+        return;
+    };
+    let Some(target) = target_rect(STEPS[i].target, &camera_q, &input_q, &loot_display_q) else {
+        *visibility = Visibility::Hidden;
+        return;
+    };
+    *visibility = Visibility::Inherited;
+
+    let spotlight = target.inflate(SPOTLIGHT_MARGIN);
+    for (side, mut node) in side_q {
+        let (left, top, width, height) = match side {
+            DimSide::Top => (0., 0., SCREEN_SIZE.x, spotlight.min.y),
+            DimSide::Bottom => (
+                0.,
+                spotlight.max.y,
+                SCREEN_SIZE.x,
+                SCREEN_SIZE.y - spotlight.max.y,
+            ),
+            DimSide::Left => (0., spotlight.min.y, spotlight.min.x, spotlight.height()),
+            DimSide::Right => (
+                spotlight.max.x,
+                spotlight.min.y,
+                SCREEN_SIZE.x - spotlight.max.x,
+                spotlight.height(),
+            ),
+        };
+        node.left = Val::Px(left);
+        node.top = Val::Px(top);
+        node.width = Val::Px(width.max(0.));
+        node.height = Val::Px(height.max(0.));
+    }
+
+    // Below the spotlight when it's in the top half of the screen, above it otherwise
+    let below = spotlight.center().y < SCREEN_SIZE.y / 2.;
+    let callout_left = (spotlight.center().x - CALLOUT_WIDTH / 2.).clamp(
+        SPOTLIGHT_MARGIN,
+        SCREEN_SIZE.x - CALLOUT_WIDTH - SPOTLIGHT_MARGIN,
+    );
+
+    if let Ok(mut callout) = callout_q.single_mut() {
+        callout.left = Val::Px(callout_left);
+        if below {
+            callout.top = Val::Px(spotlight.max.y + CALLOUT_GAP);
+            callout.bottom = Val::Auto;
+        } else {
+            callout.top = Val::Auto;
+            callout.bottom = Val::Px(SCREEN_SIZE.y - spotlight.min.y + CALLOUT_GAP);
+        }
+    }
+
+    if let Ok(mut pointer) = pointer_q.single_mut() {
+        pointer.left = Val::Px(spotlight.center().x - 1.);
+        pointer.height = Val::Px(CALLOUT_GAP);
+        pointer.top = Val::Px(if below {
+            spotlight.max.y
+        } else {
+            spotlight.min.y - CALLOUT_GAP
+        });
+    }
+}
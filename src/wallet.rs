@@ -0,0 +1,191 @@
+/// Currencies besides Loot. Some planets produce one on top of their loot, and later purchases cost
+/// a mix of them, so planets have to be unlocked and grown to afford things on the others
+use crate::gameplay_events::{BoopPassedHoop, SwarmPassedHoops};
+use crate::hoops_boops_loops::{Loop, Planet};
+use crate::loot::Loot;
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use std::fmt;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Currency {
+    Stardust,
+    Ice,
+}
+
+pub const CURRENCIES: [Currency; 2] = [Currency::Stardust, Currency::Ice];
+
+impl Currency {
+    /// The currency a planet's hoop passes also pay, if any. Each pays as much of it as loot
+    pub fn produced_on(planet: Planet) -> Option<Currency> {
+        match planet {
+            Planet::Three => Some(Currency::Stardust),
+            Planet::Five => Some(Currency::Ice),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Currency::Stardust => "stardust",
+            Currency::Ice => "ice",
+        }
+    }
+
+    /// Matches the hoops of the planet that produces it
+    pub fn color(&self) -> Color {
+        match self {
+            Currency::Stardust => Planet::Three.get_hoop_color(),
+            Currency::Ice => Planet::Five.get_hoop_color(),
+        }
+    }
+}
+
+/// How much of each currency the player has, indexed by Currency
+#[derive(Resource, Default)]
+pub struct Wallet([i32; CURRENCIES.len()]);
+
+impl Wallet {
+    pub fn get(&self, currency: Currency) -> i32 {
+        self.0[currency as usize]
+    }
+
+    pub fn add(&mut self, currency: Currency, amount: i32) {
+        let held = &mut self.0[currency as usize];
+        *held = held.saturating_add(amount);
+    }
+}
+
+/// What something costs, in loot and any other currencies
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct Price {
+    pub loot: i32,
+    /// Indexed by Currency
+    pub currencies: [i32; CURRENCIES.len()],
+}
+
+impl Price {
+    pub const fn loot(loot: i32) -> Self {
+        Price {
+            loot,
+            currencies: [0; CURRENCIES.len()],
+        }
+    }
+
+    /// Adds `amount` of `currency` to the price
+    pub const fn with(mut self, currency: Currency, amount: i32) -> Self {
+        self.currencies[currency as usize] = amount;
+        self
+    }
+
+    /// Every currency besides loot the price has, with how much
+    pub fn extras(&self) -> impl Iterator<Item = (Currency, i32)> + '_ {
+        CURRENCIES
+            .into_iter()
+            .map(|currency| (currency, self.currencies[currency as usize]))
+            .filter(|(_, amount)| *amount > 0)
+    }
+
+    /// Every part of the price times `factor`, saturating at i32::MAX instead of overflowing
+    pub fn scaled(&self, factor: f64) -> Self {
+        let scale = |amount: i32| (amount as f64 * factor).round() as i32;
+        Price {
+            loot: scale(self.loot),
+            currencies: self.currencies.map(scale),
+        }
+    }
+
+    /// How close `loot` and `wallet` are to paying it, from 0 to 1. It's as far as the currency
+    /// that's furthest from enough
+    pub fn progress(&self, loot: i32, wallet: &Wallet) -> f32 {
+        let fraction = |held: i32, needed: i32| {
+            if needed <= 0 {
+                1.
+            } else {
+                (held as f32 / needed as f32).clamp(0., 1.)
+            }
+        };
+
+        self.extras()
+            .map(|(currency, amount)| fraction(wallet.get(currency), amount))
+            .fold(fraction(loot, self.loot), f32::min)
+    }
+
+    /// Like "+10 ice", empty without any extras
+    pub fn extras_label(&self) -> String {
+        self.extras()
+            .map(|(currency, amount)| format!("+{amount} {}", currency.name()))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// Like "80 loot +15 stardust"
+impl fmt::Display for Price {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} loot", self.loot)?;
+        for (currency, amount) in self.extras() {
+            write!(f, " +{amount} {}", currency.name())?;
+        }
+        Ok(())
+    }
+}
+
+/// Loot and the Wallet together, for paying Prices
+#[derive(SystemParam)]
+pub struct Funds<'w> {
+    pub loot: ResMut<'w, Loot>,
+    pub wallet: ResMut<'w, Wallet>,
+}
+
+impl Funds<'_> {
+    pub fn can_afford(&self, price: &Price) -> bool {
+        **self.loot >= price.loot
+            && price
+                .extras()
+                .all(|(currency, amount)| self.wallet.get(currency) >= amount)
+    }
+
+    /// Pays `price` if there's enough of everything, returning whether it did
+    pub fn spend(&mut self, price: &Price) -> bool {
+        if !self.can_afford(price) {
+            return false;
+        }
+
+        **self.loot -= price.loot;
+        for (currency, amount) in price.extras() {
+            self.wallet.add(currency, -amount);
+        }
+        true
+    }
+}
+
+pub fn plugin(app: &mut App) {
+    app.init_resource::<Wallet>()
+        .add_observer(produce_on_hoop_pass)
+        .add_observer(produce_on_swarm_pass);
+}
+
+fn produce_on_hoop_pass(t: Trigger<BoopPassedHoop>, loop_q: Query<&Loop>, wallet: ResMut<Wallet>) {
+    produce(t.r#loop, t.payout, loop_q, wallet);
+}
+
+fn produce_on_swarm_pass(
+    t: Trigger<SwarmPassedHoops>,
+    loop_q: Query<&Loop>,
+    wallet: ResMut<Wallet>,
+) {
+    produce(t.r#loop, t.payout, loop_q, wallet);
+}
+
+fn produce(r#loop: Entity, payout: i32, loop_q: Query<&Loop>, mut wallet: ResMut<Wallet>) {
+    let Some(currency) = loop_q
+        .get(r#loop)
+        .ok()
+        .and_then(|r#loop| Currency::produced_on(r#loop.planet()))
+    else {
+        return;
+    };
+
+    wallet.add(currency, payout);
+}
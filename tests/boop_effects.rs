@@ -0,0 +1,34 @@
+use bevy::prelude::*;
+use hoops_boops_and_loops::boop_effects::{EffectsQuality, Particle};
+use hoops_boops_and_loops::headless::*;
+use hoops_boops_and_loops::hoops_boops_loops::*;
+
+#[test]
+fn hoop_particles_come_from_a_pool() {
+    let mut app = headless_app();
+    spawn_test_loop(&mut app, Planet::One);
+
+    // Three laps, each burst has faded long before the next one
+    advance_seconds(&mut app, 16.);
+    assert_eq!(count::<Particle>(&mut app), 6);
+
+    app.insert_resource(EffectsQuality::Off);
+    advance_seconds(&mut app, 16.);
+    assert_eq!(count::<Particle>(&mut app), 6);
+}
+
+#[test]
+fn afterimages_are_despawned_with_their_boop() {
+    let mut app = headless_app();
+    let (r#loop, _, _) = spawn_test_loop(&mut app, Planet::One);
+    apply(&mut app, AddBoop(r#loop)).unwrap();
+    app.update();
+    let sprites = count::<Sprite>(&mut app);
+
+    let boop = app.world().get::<Loop>(r#loop).unwrap().boops()[1];
+    app.world_mut().despawn(boop);
+    app.update();
+
+    // The boop and its 6 afterimages
+    assert_eq!(count::<Sprite>(&mut app), sprites - 7);
+}
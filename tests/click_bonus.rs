@@ -0,0 +1,32 @@
+use bevy::prelude::*;
+use hoops_boops_and_loops::click_bonus::ComboMeter;
+use hoops_boops_and_loops::headless::*;
+use hoops_boops_and_loops::hoops_boops_loops::*;
+use hoops_boops_and_loops::replay::*;
+
+#[test]
+fn rhythmic_planet_clicks_build_a_combo() {
+    let mut app = headless_app();
+    spawn_test_loop(&mut app, Planet::One);
+    let combo = |app: &App| app.world().resource::<ComboMeter>().combo();
+
+    for expected in 1..=4 {
+        press(&mut app, PlayerInput::ClickPlanet(Planet::One));
+        assert_eq!(combo(&app), expected);
+        for _ in 0..30 {
+            app.update();
+        }
+    }
+    // 1 + 2 + 3 + 4 from clicking, the boop passes a hoop too
+    assert_eq!(loot(&app), 11);
+
+    for _ in 0..10 {
+        app.update();
+    }
+    press(&mut app, PlayerInput::ClickPlanet(Planet::One));
+    assert_eq!(
+        combo(&app),
+        1,
+        "clicking off the rhythm should break the combo"
+    );
+}
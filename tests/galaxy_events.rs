@@ -0,0 +1,32 @@
+use bevy::prelude::*;
+use hoops_boops_and_loops::game_rng::GameRng;
+use hoops_boops_and_loops::gameplay_events::*;
+use hoops_boops_and_loops::headless::*;
+
+#[derive(Resource, Default)]
+struct StartedEvents(Vec<GalaxyEventStarted>);
+
+#[test]
+fn galaxy_events_follow_the_seed() {
+    let run = |seed: u64| {
+        let mut app = headless_app();
+        app.insert_resource(GameRng::from_seed(seed))
+            .init_resource::<StartedEvents>()
+            .add_observer(
+                |t: Trigger<GalaxyEventStarted>, mut started: ResMut<StartedEvents>| {
+                    started.0.push(*t.event())
+                },
+            );
+
+        transition_to_galaxy(&mut app);
+        advance_seconds(&mut app, 400.);
+        app.world_mut()
+            .remove_resource::<StartedEvents>()
+            .unwrap()
+            .0
+    };
+
+    let events = run(7);
+    assert!(!events.is_empty());
+    assert_eq!(events, run(7));
+}
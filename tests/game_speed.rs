@@ -0,0 +1,53 @@
+use bevy::prelude::*;
+use hoops_boops_and_loops::focus::{Focusable, Press};
+use hoops_boops_and_loops::game_speed::{GameSpeed, SpeedBtn};
+use hoops_boops_and_loops::headless::*;
+use hoops_boops_and_loops::hoops_boops_loops::*;
+
+#[test]
+fn loot_matches_at_every_speed() {
+    let mut normal = headless_app();
+    spawn_test_loop(&mut normal, Planet::One);
+    advance_seconds(&mut normal, 4. * LAP_SECONDS);
+
+    let mut fast = headless_app();
+    spawn_test_loop(&mut fast, Planet::One);
+    fast.world_mut()
+        .resource_mut::<Time<Virtual>>()
+        .set_relative_speed(4.);
+    // Four fixed ticks run on every update
+    advance_seconds(&mut fast, LAP_SECONDS);
+
+    assert_eq!(loot(&fast), 4);
+    assert_eq!(loot(&fast), loot(&normal));
+}
+
+#[test]
+fn speed_btns_can_be_pressed_without_a_mouse() {
+    let mut app = headless_app();
+    transition_to_galaxy(&mut app);
+    advance_seconds(&mut app, 1.);
+    assert_eq!(app.world().resource::<GameSpeed>().unlocked(), 1);
+
+    let press_speed_btn = |app: &mut App, i: usize| {
+        let world = app.world_mut();
+        let btn = world
+            .query::<(Entity, &SpeedBtn)>()
+            .iter(world)
+            .find(|(_, btn)| btn.0 == i)
+            .map(|(entity, _)| entity)
+            .unwrap();
+        assert!(world.get::<Focusable>(btn).is_some());
+        world.trigger_targets(Press, btn);
+        world.flush();
+        app.update();
+    };
+
+    press_speed_btn(&mut app, 1);
+    assert_eq!(app.world().resource::<GameSpeed>().speed(), 2.);
+    assert_eq!(app.world().resource::<Time<Virtual>>().relative_speed(), 2.);
+
+    // 4x stays locked until every planet is
+    press_speed_btn(&mut app, 2);
+    assert_eq!(app.world().resource::<GameSpeed>().speed(), 2.);
+}
@@ -0,0 +1,71 @@
+use bevy::prelude::*;
+use hoops_boops_and_loops::headless::*;
+use hoops_boops_and_loops::hoops_boops_loops::*;
+use hoops_boops_and_loops::locked_planets::LockedPlanet;
+
+#[derive(Resource, Default)]
+struct AllHoopsBoughtCount(u32);
+
+#[test]
+fn boops_orbit() {
+    let mut app = headless_app();
+    let (r#loop, _, _) = spawn_test_loop(&mut app, Planet::One);
+
+    advance_seconds(&mut app, 1.);
+
+    let boop = app.world().get::<Loop>(r#loop).unwrap().boops()[0];
+    let orbit = app.world().get::<Orbit>(boop).unwrap();
+    let transform = app.world().get::<Transform>(boop).unwrap();
+
+    assert!((orbit.current_loop_position - BOOP_SPEED).abs() < 0.05);
+    assert_ne!(
+        transform.translation, orbit.starting_transform.translation,
+        "orbit should move the boop away from where it started"
+    );
+}
+
+#[test]
+fn loot_increments_once_per_hoop_pass() {
+    let mut app = headless_app();
+    spawn_test_loop(&mut app, Planet::One);
+
+    advance_seconds(&mut app, LAP_SECONDS);
+    assert_eq!(loot(&app), 1);
+
+    advance_seconds(&mut app, LAP_SECONDS);
+    assert_eq!(loot(&app), 2);
+}
+
+#[test]
+fn all_hoops_bought_fires_on_eighth_hoop() {
+    let mut app = headless_app();
+    app.init_resource::<AllHoopsBoughtCount>().add_observer(
+        |_: Trigger<AllHoopsBought>, mut count: ResMut<AllHoopsBoughtCount>| count.0 += 1,
+    );
+    let (r#loop, _, _) = spawn_test_loop(&mut app, Planet::One);
+
+    // Every loop starts with a hoop
+    for _ in 2..MAX_HOOPS {
+        apply(&mut app, AddHoop(r#loop)).unwrap();
+    }
+    assert_eq!(app.world().resource::<AllHoopsBoughtCount>().0, 0);
+
+    apply(&mut app, AddHoop(r#loop)).unwrap();
+    assert_eq!(app.world().resource::<AllHoopsBoughtCount>().0, 1);
+    assert_eq!(
+        app.world().get::<Loop>(r#loop).unwrap().hoop_count(),
+        MAX_HOOPS
+    );
+
+    assert!(apply(&mut app, AddHoop(r#loop)).is_err());
+}
+
+#[test]
+fn titlescreen_to_galaxy_spawns_five_locked_planets() {
+    let mut app = headless_app();
+
+    transition_to_galaxy(&mut app);
+    app.update();
+
+    assert_eq!(count::<LockedPlanet>(&mut app), 5);
+}
@@ -0,0 +1,34 @@
+use bevy::prelude::*;
+use hoops_boops_and_loops::gameplay_events::*;
+use hoops_boops_and_loops::headless::*;
+use hoops_boops_and_loops::hoops_boops_loops::*;
+use hoops_boops_and_loops::loot::Loot;
+use hoops_boops_and_loops::prices::FIRST_PLANET_BOOP_PRICES;
+use hoops_boops_and_loops::replay::*;
+
+#[derive(Resource, Default)]
+struct Purchases(Vec<Purchased>);
+
+#[test]
+fn buying_a_boop_triggers_purchased() {
+    let mut app = headless_app();
+    app.init_resource::<Purchases>().add_observer(
+        |t: Trigger<Purchased>, mut purchases: ResMut<Purchases>| purchases.0.push(*t.event()),
+    );
+    let (r#loop, _, _) = spawn_test_loop(&mut app, Planet::One);
+
+    let price = FIRST_PLANET_BOOP_PRICES[0];
+    **app.world_mut().resource_mut::<Loot>() += price.loot;
+    press(&mut app, PlayerInput::BuyBoop(Planet::One));
+
+    assert_eq!(
+        app.world().resource::<Purchases>().0,
+        vec![Purchased {
+            kind: PurchaseKind::Boop,
+            r#loop,
+            price,
+        }]
+    );
+    assert_eq!(loot(&app), 0);
+    assert_eq!(app.world().get::<Loop>(r#loop).unwrap().boops().len(), 2);
+}
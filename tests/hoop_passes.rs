@@ -0,0 +1,69 @@
+use bevy::prelude::*;
+use bevy::time::TimeUpdateStrategy;
+use hoops_boops_and_loops::gameplay_events::*;
+use hoops_boops_and_loops::headless::*;
+use hoops_boops_and_loops::hoops_boops_loops::*;
+use std::time::Duration;
+
+#[derive(Resource, Default)]
+struct PassedHoops(Vec<BoopPassedHoop>);
+
+#[test]
+fn huge_fixed_steps_still_count_every_hoop() {
+    let run = |step: Duration| {
+        let mut app = headless_app();
+        app.init_resource::<PassedHoops>()
+            .add_observer(
+                |t: Trigger<BoopPassedHoop>, mut passed: ResMut<PassedHoops>| {
+                    passed.0.push(*t.event());
+                },
+            )
+            .insert_resource(Time::<Fixed>::from_duration(step))
+            .insert_resource(TimeUpdateStrategy::ManualDuration(step));
+        app.world_mut()
+            .resource_mut::<Time<Virtual>>()
+            .set_max_delta(step);
+
+        let (r#loop, _, _) = spawn_test_loop(&mut app, Planet::One);
+        for _ in 1..MAX_HOOPS {
+            apply(&mut app, AddHoop(r#loop)).unwrap();
+        }
+
+        // Every step size divides evenly into 6 seconds
+        for _ in 0..(6. / step.as_secs_f32()).round() as u32 {
+            app.update();
+        }
+        (loot(&app), app.world().resource::<PassedHoops>().0.len())
+    };
+
+    // A whole second per tick moves a boop over more than one hoop, six seconds over a whole lap
+    let (huge_loot, huge_passes) = run(Duration::from_secs(1));
+    let (lap_loot, lap_passes) = run(Duration::from_secs(6));
+    let (loot, passes) = run(TICK);
+
+    assert_eq!(huge_loot, loot);
+    assert_eq!(huge_passes, passes);
+    assert_eq!(lap_loot, loot);
+    assert_eq!(lap_passes, passes);
+    // Every hoop and then the first one again
+    assert_eq!(passes, 9);
+}
+
+#[test]
+fn despawned_boops_stop_passing_hoops() {
+    let mut app = headless_app();
+    app.init_resource::<PassedHoops>().add_observer(
+        |t: Trigger<BoopPassedHoop>, mut passed: ResMut<PassedHoops>| passed.0.push(*t.event()),
+    );
+    let (r#loop, _, _) = spawn_test_loop(&mut app, Planet::One);
+    apply(&mut app, AddBoop(r#loop)).unwrap();
+
+    let boop = app.world().get::<Loop>(r#loop).unwrap().boops()[0];
+    app.world_mut().despawn(boop);
+    advance_seconds(&mut app, LAP_SECONDS);
+
+    let passed = &app.world().resource::<PassedHoops>().0;
+    assert_eq!(passed.len(), 1);
+    assert_ne!(passed[0].boop, boop);
+    assert_eq!(app.world().get::<Loop>(r#loop).unwrap().boops().len(), 1);
+}
@@ -0,0 +1,63 @@
+use bevy::prelude::*;
+use bevy::time::TimeUpdateStrategy;
+use hoops_boops_and_loops::headless::*;
+use hoops_boops_and_loops::hoops_boops_loops::*;
+use hoops_boops_and_loops::loot::Loot;
+use std::f32::consts::PI;
+
+#[test]
+fn orbits_are_drawn_between_fixed_ticks() {
+    let mut app = headless_app();
+    let (r#loop, _, _) = spawn_test_loop(&mut app, Planet::One);
+    advance_seconds(&mut app, 1.);
+
+    let boop = app.world().get::<Loop>(r#loop).unwrap().boops()[0];
+    let ticked = app
+        .world()
+        .get::<Orbit>(boop)
+        .unwrap()
+        .current_loop_position;
+
+    // Half a tick, not enough for FixedUpdate to run
+    app.insert_resource(TimeUpdateStrategy::ManualDuration(TICK / 2));
+    app.update();
+
+    let orbit = app.world().get::<Orbit>(boop).unwrap();
+    assert_eq!(orbit.current_loop_position, ticked);
+
+    let transform = app.world().get::<Transform>(boop).unwrap();
+    let drawn = transform
+        .rotation
+        .to_euler(EulerRot::XYZ)
+        .2
+        .rem_euclid(2. * PI);
+    let halfway = ticked - BOOP_SPEED * TICK.as_secs_f32() / 2.;
+    assert!((drawn - halfway).abs() < 0.001);
+}
+
+#[test]
+fn boops_past_the_sprite_limit_join_the_swarm() {
+    let mut app = headless_app();
+    let (r#loop, _, _) = spawn_test_loop(&mut app, Planet::One);
+    for _ in 1..100 {
+        apply(&mut app, AddBoop(r#loop)).unwrap();
+    }
+
+    assert_eq!(count::<Boop>(&mut app), MAX_BOOP_SPRITES);
+    assert_eq!(app.world().get::<Loop>(r#loop).unwrap().boop_count(), 100);
+
+    // Every boop passes the one hoop once a lap, swarm passes are paid in whole passes as they add
+    // up so one can still be pending
+    advance_seconds(&mut app, LAP_SECONDS);
+    assert!((loot(&app) - 100).abs() <= 1);
+}
+
+#[test]
+fn loot_saturates_instead_of_overflowing() {
+    let mut app = headless_app();
+    spawn_test_loop(&mut app, Planet::One);
+    **app.world_mut().resource_mut::<Loot>() = i32::MAX;
+
+    advance_seconds(&mut app, LAP_SECONDS);
+    assert_eq!(loot(&app), i32::MAX);
+}
@@ -0,0 +1,26 @@
+use hoops_boops_and_loops::headless::*;
+use hoops_boops_and_loops::hoops_boops_loops::*;
+use hoops_boops_and_loops::loot::Loot;
+use hoops_boops_and_loops::prices::FIRST_PLANET_HOOP_PRICES;
+use hoops_boops_and_loops::quests::{QUESTS, QuestLog};
+use hoops_boops_and_loops::replay::*;
+
+#[test]
+fn buying_the_first_hoop_completes_the_first_quest() {
+    let mut app = headless_app();
+    spawn_test_loop(&mut app, Planet::One);
+
+    **app.world_mut().resource_mut::<Loot>() += FIRST_PLANET_HOOP_PRICES[0].loot;
+    press(&mut app, PlayerInput::BuyHoop(Planet::One));
+    app.update();
+
+    assert_eq!(
+        *app.world().resource::<QuestLog>(),
+        QuestLog {
+            current: 1,
+            progress: 0
+        }
+    );
+    // Plus whatever the boop earned on that tick
+    assert!(loot(&app) >= QUESTS[0].reward);
+}
@@ -0,0 +1,48 @@
+use bevy::prelude::*;
+use hoops_boops_and_loops::headless::*;
+use hoops_boops_and_loops::hoops_boops_loops::*;
+use hoops_boops_and_loops::replay::*;
+
+#[test]
+fn replay_reproduces_loot_trajectory() {
+    let mut app = headless_app();
+    press(&mut app, PlayerInput::Start);
+    advance_seconds(&mut app, 2. * LAP_SECONDS + 1.);
+    press(&mut app, PlayerInput::BuyHoop(Planet::One));
+    advance_seconds(&mut app, LAP_SECONDS);
+    press(&mut app, PlayerInput::BuyBoop(Planet::One));
+    advance_seconds(&mut app, 2. * LAP_SECONDS);
+
+    let replay = recorded_replay(app.world());
+    assert_eq!(replay.inputs.len(), 3);
+    assert_eq!(Replay::parse(&replay.to_string()).unwrap(), replay);
+
+    let mut playback = headless_app();
+    start_playback(&mut playback, replay);
+    let ticks = app.world().resource::<ReplayClock>().tick;
+    while playback.world().resource::<ReplayClock>().tick < ticks {
+        playback.update();
+    }
+
+    assert!(playback.world().resource::<Playback>().finished());
+    assert_eq!(loot(&playback), loot(&app));
+}
+
+#[test]
+fn presses_are_left_out_of_the_replay_while_recording_is_paused() {
+    let mut app = headless_app();
+    spawn_test_loop(&mut app, Planet::One);
+
+    app.world_mut().insert_resource(RecordingPaused);
+    press(&mut app, PlayerInput::BuyBoop(Planet::One));
+    app.world_mut().remove_resource::<RecordingPaused>();
+    press(&mut app, PlayerInput::BuyHoop(Planet::One));
+
+    let replay = recorded_replay(app.world());
+    let inputs: Vec<PlayerInput> = replay
+        .inputs
+        .iter()
+        .map(|recorded| recorded.input)
+        .collect();
+    assert_eq!(inputs, vec![PlayerInput::BuyHoop(Planet::One)]);
+}
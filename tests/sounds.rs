@@ -0,0 +1,151 @@
+use bevy::audio::Source;
+use bevy::prelude::*;
+use hoops_boops_and_loops::band_pass::BandPass;
+use hoops_boops_and_loops::headless::*;
+use hoops_boops_and_loops::hoops_boops_loops::*;
+use hoops_boops_and_loops::play_hoop_through_boop_sounds::note_speed;
+use hoops_boops_and_loops::replay::PlayerInput;
+use hoops_boops_and_loops::soundtrack::{StemPlayer, Theme};
+use hoops_boops_and_loops::transition_to_first_planet::{FirstPlanet, TransitionToFirstPlanet};
+use std::f32::consts::TAU;
+use std::time::Duration;
+
+#[test]
+fn hoop_notes_climb_the_scale() {
+    for planet in 1..=6 {
+        let planet = Planet::from_i32(planet).unwrap();
+        let speeds: Vec<f32> = (0..MAX_HOOPS as usize)
+            .map(|hoop_index| note_speed(planet, hoop_index))
+            .collect();
+
+        assert!(speeds.windows(2).all(|pair| pair[0] < pair[1]));
+        // Eight hoops cover an octave
+        assert!((speeds[7] / speeds[0] - 2.).abs() < 0.001);
+    }
+}
+
+#[test]
+fn unsuccessful_buys_are_panned_to_their_loop() {
+    let mut app = headless_app();
+    let (r#loop, _, _) = spawn_test_loop(&mut app, Planet::One);
+    app.world_mut()
+        .get_mut::<Transform>(r#loop)
+        .unwrap()
+        .translation
+        .x = -500.;
+
+    // Nothing to pay with
+    press(&mut app, PlayerInput::BuyHoop(Planet::One));
+    app.update();
+
+    let sounds: Vec<(Vec3, bool)> = app
+        .world_mut()
+        .query_filtered::<(&Transform, &PlaybackSettings), With<AudioPlayer>>()
+        .iter(app.world())
+        .map(|(transform, settings)| (transform.translation, settings.spatial))
+        .collect();
+    assert!(sounds.contains(&(Vec3::new(-500., 0., 0.), true)));
+}
+
+/// The themes of the stems that should be audible
+fn audible_stems(app: &mut App) -> Vec<Theme> {
+    app.world_mut()
+        .query::<&StemPlayer>()
+        .iter(app.world())
+        .filter(|stem_player| stem_player.audible())
+        .map(|stem_player| stem_player.theme())
+        .collect()
+}
+
+#[test]
+fn stems_crossfade_by_phase_and_fade_in_with_progress() {
+    let mut app = headless_app();
+    app.update();
+    assert_eq!(audible_stems(&mut app), [Theme::Titlescreen]);
+
+    apply(&mut app, TransitionToFirstPlanet);
+    let first_planet = app
+        .world_mut()
+        .query_filtered::<Entity, With<FirstPlanet>>()
+        .single(app.world())
+        .unwrap();
+    app.update();
+    assert_eq!(audible_stems(&mut app), [Theme::FirstPlanet]);
+
+    // Every loop starts with a boop
+    for _ in 1..4 {
+        apply(&mut app, AddBoop(first_planet)).unwrap();
+    }
+    app.update();
+    assert_eq!(audible_stems(&mut app), [Theme::FirstPlanet; 2]);
+
+    for _ in 1..MAX_HOOPS {
+        apply(&mut app, AddHoop(first_planet)).unwrap();
+    }
+    app.update();
+    assert_eq!(audible_stems(&mut app), [Theme::Galaxy]);
+
+    for _ in 4..24 {
+        apply(&mut app, AddBoop(first_planet)).unwrap();
+    }
+    app.update();
+    assert_eq!(audible_stems(&mut app), [Theme::Galaxy; 2]);
+}
+
+/// A stereo sine wave with a different frequency on each channel
+struct StereoSine {
+    frequencies: [f32; 2],
+    sample: usize,
+}
+
+impl Iterator for StereoSine {
+    type Item = f32;
+
+    fn next(&mut self) -> Option<f32> {
+        let (frame, channel) = (self.sample / 2, self.sample % 2);
+        self.sample += 1;
+        let t = frame as f32 / SAMPLE_RATE as f32;
+        Some((TAU * self.frequencies[channel] * t).sin())
+    }
+}
+
+impl Source for StereoSine {
+    fn current_frame_len(&self) -> Option<usize> {
+        None
+    }
+
+    fn channels(&self) -> u16 {
+        2
+    }
+
+    fn sample_rate(&self) -> u32 {
+        SAMPLE_RATE
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        None
+    }
+}
+
+const SAMPLE_RATE: u32 = 44_100;
+
+#[test]
+fn band_pass_filters_each_channel_on_its_own() {
+    let sine = StereoSine {
+        frequencies: [100., 5_000.],
+        sample: 0,
+    };
+    // A second to settle, then a second to measure
+    let samples: Vec<f32> = BandPass::new(sine, 20, 500)
+        .skip(2 * SAMPLE_RATE as usize)
+        .take(2 * SAMPLE_RATE as usize)
+        .collect();
+
+    let rms = |channel: usize| {
+        let squares = samples.iter().skip(channel).step_by(2).map(|s| s * s);
+        (squares.sum::<f32>() / SAMPLE_RATE as f32).sqrt()
+    };
+    // A full sine wave has an RMS of 1 / sqrt(2)
+    assert!(rms(0) > 0.65, "100 Hz is in the band, got {}", rms(0));
+    assert!(rms(1) < 0.01, "5 kHz is out of the band, got {}", rms(1));
+}
@@ -0,0 +1,31 @@
+use hoops_boops_and_loops::headless::*;
+use hoops_boops_and_loops::hoops_boops_loops::*;
+
+#[test]
+fn a_perfect_loop_doubles_the_payout() {
+    let mut app = headless_app();
+    let (r#loop, _, _) = spawn_test_loop(&mut app, Planet::One);
+    for _ in 1..MAX_HOOPS {
+        apply(&mut app, AddHoop(r#loop)).unwrap();
+    }
+
+    // Just past the first hoop on the second lap
+    advance_seconds(&mut app, 6.);
+
+    let boop = app.world().get::<Loop>(r#loop).unwrap().boops()[0];
+    assert_eq!(app.world().get::<Boop>(boop).unwrap().streak(), 9);
+    // 7 hoops at 1, then 2 each for the 8th and 9th in a row, plus the perfect loop bonus
+    assert_eq!(loot(&app), 7 + 2 + 2 + PERFECT_LOOP_BONUS);
+}
+
+#[test]
+fn an_empty_hoop_slot_breaks_the_streak() {
+    let mut app = headless_app();
+    let (r#loop, _, _) = spawn_test_loop(&mut app, Planet::One);
+
+    advance_seconds(&mut app, 2. * LAP_SECONDS);
+
+    let boop = app.world().get::<Loop>(r#loop).unwrap().boops()[0];
+    assert_eq!(app.world().get::<Boop>(boop).unwrap().streak(), 0);
+    assert_eq!(loot(&app), 2);
+}
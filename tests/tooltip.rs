@@ -0,0 +1,21 @@
+use bevy::prelude::*;
+use hoops_boops_and_loops::headless::*;
+use hoops_boops_and_loops::hoops_boops_loops::*;
+use hoops_boops_and_loops::tooltip::{Hovered, Tooltip, show_tooltip};
+
+#[test]
+fn moon_btn_tooltips_show_counts_and_the_next_price() {
+    let mut app = headless_app();
+    let (_, boop_btn, hoop_btn) = spawn_test_loop(&mut app, Planet::One);
+
+    // Only the hovered btn's tooltip is filled in
+    let hover = |app: &mut App, btn| {
+        show_tooltip(btn, &mut app.world_mut().resource_mut::<Hovered>());
+        app.update();
+        app.world().get::<Tooltip>(btn).unwrap().0.clone()
+    };
+    assert!(hover(&mut app, boop_btn).starts_with("Boops 1, no limit\n"));
+    let hoop_tooltip = hover(&mut app, hoop_btn);
+    assert!(hoop_tooltip.starts_with("Hoops 1/8\n"));
+    assert!(hoop_tooltip.ends_with("Then 5 loot"));
+}
@@ -0,0 +1,68 @@
+use bevy::prelude::*;
+use hoops_boops_and_loops::gameplay_events::*;
+use hoops_boops_and_loops::headless::*;
+use hoops_boops_and_loops::hoops_boops_loops::*;
+use hoops_boops_and_loops::loot::Loot;
+use hoops_boops_and_loops::prices::LANE_PRICES;
+use hoops_boops_and_loops::replay::*;
+use hoops_boops_and_loops::wallet::{Currency, Wallet};
+
+#[test]
+fn lanes_carry_boops_to_the_next_planet() {
+    let mut app = headless_app();
+    let (first, _, _) = spawn_test_loop(&mut app, Planet::One);
+    apply(&mut app, AddBoop(first)).unwrap();
+    let (second, _, _) = spawn_test_loop(&mut app, Planet::Two);
+    app.world_mut().trigger(PlanetUnlocked {
+        planet: Planet::Two,
+    });
+    app.world_mut().flush();
+
+    **app.world_mut().resource_mut::<Loot>() = LANE_PRICES[0].loot;
+    press(&mut app, PlayerInput::BuildLane(Planet::One));
+    assert_eq!(loot(&app), 0);
+
+    // Taken after 3 laps, then 4 seconds along the lane
+    advance_seconds(&mut app, 3. * LAP_SECONDS + 4.5);
+
+    let boop_count = |app: &App, r#loop| app.world().get::<Loop>(r#loop).unwrap().boop_count();
+    assert_eq!(boop_count(&app, first), 1);
+    assert_eq!(boop_count(&app, second), 2);
+}
+
+#[test]
+fn stardust_from_planet_three_pays_for_lanes() {
+    let mut app = headless_app();
+    let (three, _, _) = spawn_test_loop(&mut app, Planet::Three);
+    apply(&mut app, AddBoop(three)).unwrap();
+
+    // Every hoop pass on planet three pays as much stardust as loot
+    advance_seconds(&mut app, LAP_SECONDS);
+    let stardust = |app: &App| app.world().resource::<Wallet>().get(Currency::Stardust);
+    assert!(stardust(&app) > 0);
+    assert_eq!(stardust(&app), loot(&app));
+
+    spawn_test_loop(&mut app, Planet::Six);
+    app.world_mut().trigger(PlanetUnlocked {
+        planet: Planet::Six,
+    });
+    app.world_mut().flush();
+
+    // The lane from three to six needs stardust on top of its loot
+    let price = LANE_PRICES[2];
+    **app.world_mut().resource_mut::<Loot>() = price.loot;
+    let held = stardust(&app);
+    app.world_mut()
+        .resource_mut::<Wallet>()
+        .add(Currency::Stardust, -held);
+    press(&mut app, PlayerInput::BuildLane(Planet::Three));
+    assert_eq!(loot(&app), price.loot);
+
+    app.world_mut().resource_mut::<Wallet>().add(
+        Currency::Stardust,
+        price.currencies[Currency::Stardust as usize],
+    );
+    press(&mut app, PlayerInput::BuildLane(Planet::Three));
+    assert_eq!(loot(&app), 0);
+    assert_eq!(stardust(&app), 0);
+}
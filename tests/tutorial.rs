@@ -0,0 +1,30 @@
+use bevy::prelude::*;
+use hoops_boops_and_loops::headless::*;
+use hoops_boops_and_loops::hoops_boops_loops::*;
+use hoops_boops_and_loops::loot::Loot;
+use hoops_boops_and_loops::prices::{FIRST_PLANET_BOOP_PRICES, FIRST_PLANET_HOOP_PRICES};
+use hoops_boops_and_loops::replay::*;
+use hoops_boops_and_loops::tutorial::{Tutorial, load_tutorial_done};
+
+#[test]
+fn tutorial_waits_for_the_purchase_it_asks_for() {
+    let mut app = headless_app();
+    app.insert_resource(Tutorial::Step(0));
+    spawn_test_loop(&mut app, Planet::One);
+    let tutorial = |app: &App| *app.world().resource::<Tutorial>();
+
+    // Asks for a boop first, so a hoop doesn't count
+    **app.world_mut().resource_mut::<Loot>() += FIRST_PLANET_HOOP_PRICES[0].loot;
+    press(&mut app, PlayerInput::BuyHoop(Planet::One));
+    assert_eq!(tutorial(&app), Tutorial::Step(0));
+
+    **app.world_mut().resource_mut::<Loot>() += FIRST_PLANET_BOOP_PRICES[0].loot;
+    press(&mut app, PlayerInput::BuyBoop(Planet::One));
+    assert_eq!(tutorial(&app), Tutorial::Step(1));
+}
+
+#[test]
+fn only_a_missing_tutorial_save_is_a_first_launch() {
+    assert!(!load_tutorial_done("no-such-dir/tutorial.save").unwrap());
+    assert!(load_tutorial_done("Cargo.toml").unwrap());
+}
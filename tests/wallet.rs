@@ -0,0 +1,60 @@
+use bevy::prelude::*;
+use hoops_boops_and_loops::headless::*;
+use hoops_boops_and_loops::hoops_boops_loops::*;
+use hoops_boops_and_loops::loot::Loot;
+use hoops_boops_and_loops::prices::{FIRST_PLANET_BOOP_PRICES, GALAXY_HOOP_PRICES};
+use hoops_boops_and_loops::replay::*;
+use hoops_boops_and_loops::wallet::{Currency, Price, Wallet};
+
+#[test]
+fn the_last_galaxy_hoops_cost_stardust_and_ice() {
+    let mut app = headless_app();
+    let world = app.world_mut();
+    let asset_server = world.resource::<AssetServer>().clone();
+    let (r#loop, _, _) = spawn_loop(
+        LoopInfo {
+            position: Vec2::ZERO,
+            planet: Planet::Two,
+            boop_prices: FIRST_PLANET_BOOP_PRICES,
+            hoop_prices: GALAXY_HOOP_PRICES,
+        },
+        &mut world.commands(),
+        &asset_server,
+    );
+    world.flush();
+
+    // Loops start with a hoop, so one less price than hoops is ever charged
+    let charged = &GALAXY_HOOP_PRICES[..MAX_HOOPS as usize - 1];
+    **app.world_mut().resource_mut::<Loot>() = charged.iter().map(|price| price.loot).sum();
+    let mut wallet = app.world_mut().resource_mut::<Wallet>();
+    wallet.add(Currency::Stardust, 10);
+    wallet.add(Currency::Ice, 10);
+
+    for _ in charged {
+        press(&mut app, PlayerInput::BuyHoop(Planet::Two));
+    }
+
+    let wallet = app.world().resource::<Wallet>();
+    assert_eq!(
+        app.world().get::<Loop>(r#loop).unwrap().hoop_count(),
+        MAX_HOOPS
+    );
+    assert_eq!(loot(&app), 0);
+    assert_eq!(wallet.get(Currency::Stardust), 0);
+    assert_eq!(wallet.get(Currency::Ice), 0);
+}
+
+#[test]
+fn price_progress_is_held_back_by_the_scarcest_currency() {
+    let price = Price::loot(100).with(Currency::Ice, 10);
+    let mut wallet = Wallet::default();
+    wallet.add(Currency::Ice, 10);
+    assert_eq!(price.progress(50, &wallet), 0.5);
+
+    wallet.add(Currency::Ice, -8);
+    assert_eq!(price.progress(50, &wallet), 0.2);
+    assert_eq!(price.progress(500, &wallet), 0.2);
+
+    wallet.add(Currency::Ice, 8);
+    assert_eq!(price.progress(500, &wallet), 1.);
+}